@@ -18,13 +18,12 @@ fn main() -> Result<(), Error> {
     let model_tpl = r#"
     use serde::{Serialize, Deserialize};
     use actix_restful::{
-        HttpCreate,
-        HttpFindListDelete,
-        HttpUpdate,
-        Model,
+        Deletable,
+        Findable,
+        Listable,
         NewModel,
         UpdatableModel,
-        RestfulPathInfo
+        RequestContext
     };
     use actix_restful_derive::{HttpCreate, HttpFindListDelete, HttpUpdate, actix_restful_info};
 
@@ -32,7 +31,6 @@ fn main() -> Result<(), Error> {
     use async_trait::async_trait;
     use std::default::Default;
     use actix_web;
-    use serde_json;
     
     #[derive(Default, Deserialize)]
     struct FindQuery {}
@@ -49,45 +47,70 @@ fn main() -> Result<(), Error> {
     type Id = i64;
     
     #[derive(Default, Serialize, Deserialize, HttpFindListDelete)]
-    #[http_find_list_delete(Id, FindQuery, ListQuery, DeleteQuery, AppState)]
     #[actix_restful_info(scope = "/v1", path = "{entity_lower_case}")]
     struct {entity} {
         id: Id,
     }
     
     #[async_trait]
-    impl Model<Id, FindQuery, ListQuery, ListResult, DeleteQuery, DeleteResult, AppState> for {entity} {
-        async fn find(id: Id, _query: &FindQuery, _state: &AppState) -> Result<Box<{entity}>> {
+    impl Findable for {entity} {
+        type Id = Id;
+        type FindQuery = FindQuery;
+        type State = AppState;
+
+        async fn find(id: Id, _query: &FindQuery, _state: &AppState, _ctx: &RequestContext) -> Result<Box<{entity}>> {
             // fetch from somwhere with id
         }
-        async fn list(_query: &ListQuery, _state: &AppState) -> Result<ListResult> {
+    }
+
+    #[async_trait]
+    impl Listable for {entity} {
+        type ListQuery = ListQuery;
+        type ListResult = ListResult;
+        type State = AppState;
+
+        async fn list(_query: &ListQuery, _state: &AppState, _ctx: &RequestContext) -> Result<ListResult> {
             // list
         }
-        async fn delete(mut self: Self, _query: &DeleteQuery, _state: &AppState) -> Result<DeleteResult> {
+    }
+
+    #[async_trait]
+    impl Deletable for {entity} {
+        type DeleteQuery = DeleteQuery;
+        type DeleteResult = DeleteResult;
+
+        async fn delete(mut self: Self, _query: &DeleteQuery, _state: &AppState, _ctx: &RequestContext) -> Result<DeleteResult> {
             // hard or soft delete
         }
     }
     
     #[derive(Serialize, Deserialize, HttpCreate)]
-    #[http_create(SaveQuery, AppState)]
     struct New{entity} {
 
     }
     #[async_trait]
-    impl NewModel<{entity}, SaveQuery, AppState> for New{entity} {
-        async fn save(self: Self, _query: &SaveQuery, _state: &AppState) -> Result<{entity}> {
+    impl NewModel for New{entity} {
+        type Output = {entity};
+        type Query = SaveQuery;
+        type State = AppState;
+
+        async fn save(self: Self, _query: &SaveQuery, _state: &AppState, _ctx: &RequestContext) -> Result<{entity}> {
             // persist
         }
     }
     
     #[derive(Serialize, Deserialize, HttpUpdate)]
-    #[http_update(Id, UpdateQuery, {entity}, FindQuery, AppState)]
     struct Updatable{entity} {
         id: Id,
     }
     #[async_trait]
-    impl UpdatableModel<Updatable{entity}, UpdateQuery, AppState> for Updatable{entity} {
-        async fn update(mut self: Self, _query: &UpdateQuery, _state: &AppState) -> Result<Updatable{entity}> {
+    impl UpdatableModel for Updatable{entity} {
+        type Model = {entity};
+        type Output = Updatable{entity};
+        type Query = UpdateQuery;
+        type State = AppState;
+
+        async fn update(mut self: Self, _id: Id, _current: {entity}, _query: &UpdateQuery, _state: &AppState, _ctx: &RequestContext) -> Result<Updatable{entity}> {
             // update in db
         }
     }
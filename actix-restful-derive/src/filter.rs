@@ -0,0 +1,126 @@
+use crate::rename::serialized_name;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::Result as SynResult;
+
+/// The type of the values of a field : the item of an `Option` or a `Vec`, or the type itself
+fn value_type(ty: &syn::Type) -> Option<&syn::Ident> {
+    let segment = match ty {
+        syn::Type::Path(path) if path.qself.is_none() => path.path.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident == "Option" || segment.ident == "Vec" {
+        if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+            if let Some(syn::GenericArgument::Type(item)) = args.args.first() {
+                return value_type(item);
+            }
+        }
+    }
+    Some(&segment.ident)
+}
+
+/// The `FilterKind` of a field of the type `ty`
+fn filter_kind(ty: &syn::Type) -> proc_macro2::TokenStream {
+    let kind = match value_type(ty).map(|ident| ident.to_string()).as_deref() {
+        Some("i8" | "i16" | "i32" | "i64" | "isize" | "u8" | "u16" | "u32" | "u64" | "usize") => quote! { Integer },
+        Some("f32" | "f64") => quote! { Float },
+        Some("bool") => quote! { Boolean },
+        _ => quote! { String },
+    };
+    quote! { actix_restful::FilterKind::#kind }
+}
+
+type NamedFields = syn::punctuated::Punctuated<syn::Field, syn::Token![,]>;
+
+/// The fields of the model, whose names are listed in the parameter `param`
+pub(crate) fn named_fields<'a>(ast: &'a syn::DeriveInput, param: &str) -> SynResult<&'a NamedFields> {
+    match &ast.data {
+        syn::Data::Struct(syn::DataStruct { fields: syn::Fields::Named(fields), .. }) => Ok(&fields.named),
+        _ => Err(syn::Error::new_spanned(
+            &ast.ident,
+            format!("the `{}` parameter requires a struct with named fields", param),
+        )),
+    }
+}
+
+/// The field `name` listed in the parameter `param`, `type` naming the field `r#type`
+pub(crate) fn find_field<'a>(
+    ast: &syn::DeriveInput,
+    fields: &'a NamedFields,
+    name: &str,
+    param: &str,
+) -> SynResult<&'a syn::Field> {
+    let field = fields.iter().find(|field| field.ident.as_ref().is_some_and(|ident| ident.unraw() == name));
+    field.ok_or_else(|| {
+        let names: Vec<String> = fields.iter().filter_map(|field| field.ident.as_ref()).map(|i| i.unraw().to_string()).collect();
+        syn::Error::new_spanned(
+            &ast.ident,
+            format!("unknown field `{}` in `{}`, expected one of `{}`", name, param, names.join("`, `")),
+        )
+    })
+}
+
+/// The name of the field `name` listed in the parameter `param` in the JSON serialization of the model,
+/// the name read from the query strings
+pub(crate) fn field_serialized_name(
+    ast: &syn::DeriveInput,
+    fields: &NamedFields,
+    name: &str,
+    param: &str,
+) -> SynResult<(String, syn::Type)> {
+    let field = find_field(ast, fields, name, param)?;
+    let serialized = serialized_name(ast, field).ok_or_else(|| {
+        syn::Error::new_spanned(
+            &ast.ident,
+            format!("the field `{}` in `{}` is skipped or flattened by serde, it is missing from the JSON", name, param),
+        )
+    })?;
+    Ok((serialized, field.ty.clone()))
+}
+
+/// The `FilterKind` named in the `filter` parameter, `id: integer`, for the types which are not recognized
+fn explicit_kind(kind: &str) -> Option<proc_macro2::TokenStream> {
+    let kind = match kind {
+        "string" => quote! { String },
+        "integer" => quote! { Integer },
+        "float" => quote! { Float },
+        "boolean" => quote! { Boolean },
+        _ => return None,
+    };
+    Some(quote! { actix_restful::FilterKind::#kind })
+}
+
+/// The `Filterable` implementation of the model, from the fields listed in its `filter` parameter
+pub(crate) fn impl_filterable(ast: &syn::DeriveInput, filter: Option<&str>) -> SynResult<proc_macro2::TokenStream> {
+    let filter = match filter {
+        Some(filter) => filter,
+        None => return Ok(quote! {}),
+    };
+    let fields = named_fields(ast, "filter")?;
+    let mut filter_fields = Vec::new();
+    for param in filter.split(',').map(str::trim).filter(|param| !param.is_empty()) {
+        let (name, kind) = match param.split_once(':') {
+            Some((name, kind)) => (name.trim(), Some(kind.trim())),
+            None => (param, None),
+        };
+        let (serialized, ty) = field_serialized_name(ast, fields, name, "filter")?;
+        let kind = match kind {
+            Some(kind) => explicit_kind(kind).ok_or_else(|| {
+                syn::Error::new_spanned(
+                    &ast.ident,
+                    format!("unknown filter kind `{}`, expected `string`, `integer`, `float` or `boolean`", kind),
+                )
+            })?,
+            None => filter_kind(&ty),
+        };
+        filter_fields.push(quote! { actix_restful::FilterField { name: #serialized, kind: #kind } });
+    }
+    let name = &ast.ident;
+    Ok(quote! {
+        impl actix_restful::Filterable for #name {
+            fn filter_fields() -> &'static [actix_restful::FilterField] {
+                &[#(#filter_fields),*]
+            }
+        }
+    })
+}
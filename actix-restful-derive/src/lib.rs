@@ -1,96 +1,456 @@
 extern crate proc_macro;
 use darling::FromMeta;
 use quote::{quote, ToTokens};
+use syn::punctuated::Punctuated;
 use syn::{ self, Result as SynResult, AttributeArgs, Token, parse_macro_input };
 
-struct HttpCreateDeriveParams (syn::Ident, syn::Ident);
-impl syn::parse::Parse for HttpCreateDeriveParams {
+mod filter;
+mod rename;
+mod restful;
+mod sort;
+mod validate;
+
+/// A parameter of a derive attribute : a type, `key = Type`, or the `compat` flag
+enum DeriveParam {
+    Positional(syn::Type),
+    Named(syn::Ident, syn::Type),
+    Compat(syn::Ident),
+}
+
+impl syn::parse::Parse for DeriveParam {
     fn parse(input: syn::parse::ParseStream) -> SynResult<Self> {
-        let content;
-        syn::parenthesized!(content in input);
-        let query = content.parse()?;
-        content.parse::<Token![,]>()?;
-        let app_state = content.parse()?;
-        Ok(HttpCreateDeriveParams(query, app_state))
+        let fork = input.fork();
+        if let Ok(flag) = fork.parse::<syn::Ident>() {
+            if flag == "compat" && (fork.is_empty() || fork.peek(Token![,])) {
+                input.parse::<syn::Ident>()?;
+                return Ok(DeriveParam::Compat(flag));
+            }
+        }
+        if !(input.peek(syn::Ident) && input.peek2(Token![=])) {
+            return Ok(DeriveParam::Positional(input.parse()?));
+        }
+        let key = input.parse()?;
+        input.parse::<Token![=]>()?;
+        // `key = "Type"` is accepted as well, as in `actix_restful_info`
+        let value = if input.peek(syn::LitStr) {
+            input.parse::<syn::LitStr>()?.parse()?
+        } else {
+            input.parse()?
+        };
+        Ok(DeriveParam::Named(key, value))
     }
 }
-#[proc_macro_derive(HttpCreate, attributes(http_create))]
-pub fn http_create(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let ast = syn::parse(input).unwrap();
-    impl_http_create_macro(&ast)
+
+fn no_query() -> Option<syn::Type> {
+    Some(syn::parse_quote!(actix_restful::NoQuery))
+}
+
+fn no_state() -> Option<syn::Type> {
+    Some(syn::parse_quote!(()))
+}
+
+/// The model traits called by the handlers : the `Nested` ones for a model with a parent, and the generic traits
+/// of `actix_restful::compat` when the `compat` flag is given, `#[http_find_list_delete(compat, Id, ..)]`
+struct ModelTraits {
+    parent: Option<syn::Path>,
+    compat: bool,
+}
+
+impl ModelTraits {
+    /// The model trait named `base`, or its `Nested` counterpart for a model with a parent
+    fn name(&self, base: &str) -> syn::Ident {
+        match self.parent {
+            Some(_) => quote::format_ident!("Nested{}", base),
+            None => quote::format_ident!("{}", base),
+        }
+    }
+
+    /// The default of a parameter of the derives : the associated type `assoc` of the model trait `base`
+    /// implemented by `ty`. The generic traits of `compat` have no associated types, the parameter
+    /// then defaults to `compat_default`.
+    fn assoc_type(&self, ty: &impl ToTokens, base: &str, assoc: &str, compat_default: Option<syn::Type>) -> Option<syn::Type> {
+        if self.compat {
+            return compat_default;
+        }
+        let model_trait = self.name(base);
+        let assoc = quote::format_ident!("{}", assoc);
+        Some(syn::parse_quote!(<#ty as actix_restful::#model_trait>::#assoc))
+    }
+
+    /// The call of the method `method` of the model trait `base` implemented by `ty`.
+    ///
+    /// The methods of the generic traits of `compat` are called by name, as these traits are
+    /// imported along with the derives.
+    fn call(&self, ty: &impl ToTokens, base: &str, method: &str) -> proc_macro2::TokenStream {
+        let method = quote::format_ident!("{}", method);
+        if self.compat {
+            return quote! { #ty::#method };
+        }
+        let model_trait = self.name(base);
+        quote! { <#ty as actix_restful::#model_trait>::#method }
+    }
 }
 
-fn impl_http_create_macro(ast: &syn::DeriveInput) -> proc_macro::TokenStream {
-    let attribute = ast.attrs.iter().filter(
-        |a| a.path.segments.len() == 1 && a.path.segments[0].ident == "http_create"
-    ).nth(0).expect("http_create attribute required for deriving HttpCreate!");
+/// The parameters read from a derive attribute, taken one by one along with their default
+struct DeriveParams<'a, const N: usize> {
+    derive_name: &'a str,
+    attribute_name: &'a str,
+    attribute: Option<&'a syn::Attribute>,
+    names: [&'a str; N],
+    values: [Option<syn::Type>; N],
+    parent: Option<syn::Path>,
+    compat: bool,
+}
 
-    let parameter: HttpCreateDeriveParams = syn::parse2(attribute.tokens.clone()).expect("Invalid http_create attribute!");
-    let HttpCreateDeriveParams(query, app_state) = parameter;
+impl<'a, const N: usize> DeriveParams<'a, N> {
+    /// The parameter `name`, or `default` when it was omitted
+    fn get(&mut self, name: &str, default: Option<syn::Type>) -> SynResult<syn::Type> {
+        let value = self.names.iter().position(|n| *n == name).and_then(|index| self.values[index].take());
+        value.or(default).ok_or_else(|| {
+            let message = format!(
+                "`#[derive({})]` requires the parameter `{}` : `#[{}({} = ..)]`",
+                self.derive_name, name, self.attribute_name, name
+            );
+            match self.attribute {
+                Some(attribute) => syn::Error::new_spanned(attribute, message),
+                None => syn::Error::new(proc_macro2::Span::call_site(), message),
+            }
+        })
+    }
+}
 
+/// Reads the parameters of the `attribute_name` attribute of `#[derive(derive_name)]`, the types named by `names`.
+///
+/// They are either all given in order, `#[http_create(SaveQuery, AppState)]`, or by name, `#[http_create(state = AppState)]`,
+/// the omitted ones taking their default. The attribute can be left out when every parameter has a default.
+/// The derives of nested models accept a `parent = Model` parameter when `with_parent` is set, and the `compat` flag
+/// selects the generic traits of `actix_restful::compat`.
+fn derive_params<'a, const N: usize>(
+    ast: &'a syn::DeriveInput,
+    derive_name: &'a str,
+    attribute_name: &'a str,
+    names: [&'a str; N],
+    with_parent: bool,
+) -> SynResult<DeriveParams<'a, N>> {
+    if !ast.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &ast.generics,
+            format!("`#[derive({})]` does not support generic types", derive_name),
+        ));
+    }
+    let attribute = ast.attrs.iter().find(|a| a.path.is_ident(attribute_name));
+    let params = match attribute {
+        Some(attribute) => attribute.parse_args_with(Punctuated::<DeriveParam, Token![,]>::parse_terminated)?,
+        None => Punctuated::new(),
+    };
+    let mut values: [Option<syn::Type>; N] = std::array::from_fn(|_| None);
+    let mut positional = 0;
+    let mut named = false;
+    let mut parent = None;
+    let mut compat = false;
+    for param in params {
+        match param {
+            DeriveParam::Compat(flag) if compat => {
+                return Err(syn::Error::new(flag.span(), "duplicate parameter `compat`"));
+            }
+            DeriveParam::Compat(_) => compat = true,
+            DeriveParam::Positional(value) if named || parent.is_some() => {
+                return Err(syn::Error::new_spanned(value, "positional parameters can't follow named parameters"));
+            }
+            DeriveParam::Positional(value) => {
+                if positional < N {
+                    values[positional] = Some(value);
+                }
+                positional += 1;
+            }
+            DeriveParam::Named(key, _) if key == "parent" && !with_parent => {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("unknown parameter `parent`, the parent of a `{}` is declared in `actix_restful_info`", derive_name),
+                ));
+            }
+            DeriveParam::Named(key, value) if key == "parent" => match value {
+                syn::Type::Path(value) if value.qself.is_none() => parent = Some(value.path),
+                value => return Err(syn::Error::new_spanned(value, "expected `parent = Model`")),
+            },
+            DeriveParam::Named(key, value) => {
+                let index = names.iter().position(|name| key == name).ok_or_else(|| {
+                    let mut expected = names.join("`, `");
+                    if with_parent {
+                        expected.push_str("`, `parent");
+                    }
+                    syn::Error::new(key.span(), format!("unknown parameter `{}`, expected one of `{}`", key, expected))
+                })?;
+                if values[index].is_some() {
+                    return Err(syn::Error::new(key.span(), format!("duplicate parameter `{}`", key)));
+                }
+                values[index] = Some(value);
+                named = true;
+            }
+        }
+    }
+    if positional > 0 && positional != N {
+        return Err(syn::Error::new_spanned(
+            attribute,
+            format!("expected {} parameters, found {} : `#[{}({})]`", N, positional, attribute_name, names.join(", ")),
+        ));
+    }
+    Ok(DeriveParams {
+        derive_name,
+        attribute_name,
+        attribute,
+        names,
+        values,
+        parent,
+        compat,
+    })
+}
+
+/// The statements of a handler building the `RequestContext` and looking up the parent of a nested model,
+/// the argument handing the id of the parent to the model methods, and the one handing them the context.
+/// The parent id is empty for a model without parent, the context for the model traits of `compat`.
+fn parent_lookup(
+    traits: &ModelTraits,
+    app_state: &syn::Type,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let (context, ctx) = if traits.compat {
+        (quote! {}, quote! {})
+    } else {
+        (quote! { let ctx = actix_restful::RequestContext::from_req(&req); }, quote! { , &ctx })
+    };
+    match &traits.parent {
+        Some(parent) => {
+            let lookup_ctx = if traits.compat {
+                quote! { &actix_restful::RequestContext::from_req(&req) }
+            } else {
+                quote! { &ctx }
+            };
+            (
+                quote! {
+                    #context
+                    let parent_id = match <#parent as actix_restful::ParentLookup<#app_state>>::lookup(&req, &state, #lookup_ctx).await {
+                        Ok(parent_id) => parent_id,
+                        Err(err) => return Err(err.to_response(&req))
+                    };
+                },
+                quote! { parent_id.clone(), },
+                ctx,
+            )
+        }
+        None => (context, quote! {}, ctx),
+    }
+}
+
+/// The statement answering 422 when the payload `payload` implements `actix_restful::Validate` and fails it
+fn validate_payload(payload: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    quote! {
+        {
+            #[allow(unused_imports)]
+            use actix_restful::{SkipValidation as _, ValidatePayload as _};
+            if let Err(err) = (&actix_restful::Validation(&#payload)).validate_payload() {
+                return Err(err.to_response(&req));
+            }
+        }
+    }
+}
+
+#[proc_macro_derive(HttpCreate, attributes(http_create))]
+pub fn http_create(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast = parse_macro_input!(input as syn::DeriveInput);
+    impl_http_create_macro(&ast).unwrap_or_else(|err| err.to_compile_error()).into()
+}
+
+fn impl_http_create_macro(ast: &syn::DeriveInput) -> SynResult<proc_macro2::TokenStream> {
+    let mut params = derive_params(ast, "HttpCreate", "http_create", ["query", "state"], true)?;
     let name = &ast.ident;
-    let gen = quote! {
-        #[async_trait]
-        impl HttpCreate<#query, #app_state> for #name {
-            async fn http_create(payload: actix_web::web::Json<Box<#name>>, query: actix_web::web::Query<#query>, state: actix_web::web::Data<#app_state>) -> Result<actix_web::HttpResponse, actix_web::HttpResponse>{
+    let traits = ModelTraits { parent: params.parent.clone(), compat: params.compat };
+    let query = params.get("query", traits.assoc_type(name, "NewModel", "Query", no_query()))?;
+    let app_state = params.get("state", traits.assoc_type(name, "NewModel", "State", no_state()))?;
+    Ok(gen_http_create(name, &query, &app_state, &traits))
+}
+
+/// The `HttpCreate` implementation of the payload `name`
+fn gen_http_create(name: &impl ToTokens, query: &syn::Type, app_state: &syn::Type, traits: &ModelTraits) -> proc_macro2::TokenStream {
+    let (lookup, parent_id, ctx) = parent_lookup(traits, app_state);
+    let save = traits.call(name, "NewModel", "save");
+    let validate = validate_payload(quote! { *to_save });
+    quote! {
+        #[actix_restful::async_trait(?Send)]
+        impl actix_restful::HttpCreate<#query, #app_state> for #name {
+            async fn http_create(req: actix_web::HttpRequest, payload: actix_restful::JsonBody<Box<#name>>, query: actix_web::web::Query<#query>, state: actix_web::web::Data<#app_state>) -> std::result::Result<actix_web::HttpResponse, actix_web::HttpResponse>{
                 let params = query.into_inner();
                 let to_save = payload.into_inner();
-                let result = to_save.save(&params, &state).await;
+                #lookup
+                #validate
+                let result = #save(*to_save, #parent_id &params, &state #ctx).await;
                 match result {
-                    Ok(res) => Ok(actix_web::HttpResponse::Ok().body(serde_json::json!(res))),
-                    Err(err) => Err(actix_web::HttpResponse::InternalServerError().body(err.to_string()))
+                    Ok(res) => Ok(actix_restful::created_response(&req, &res)),
+                    Err(err) => Err(actix_restful::RestError::from(err).to_response(&req))
                 }
             }
         }
-    };
-    gen.into()
+        impl actix_restful::CreateRoute for #name {
+            fn create_route() -> actix_web::Route {
+                actix_web::web::post().to(<#name as actix_restful::HttpCreate<#query, #app_state>>::http_create)
+            }
+        }
+    }
 }
 
-struct HttpFindListDeleteDeriveParams (syn::Ident, syn::Ident, syn::Ident, syn::Ident, syn::Ident);
-impl syn::parse::Parse for HttpFindListDeleteDeriveParams {
-    fn parse(input: syn::parse::ParseStream) -> SynResult<Self> {
-        let content;
-        syn::parenthesized!(content in input);
-        let id = content.parse()?;
-        content.parse::<Token![,]>()?;
-        let find_query = content.parse()?;
-        content.parse::<Token![,]>()?;
-        let list_query = content.parse()?;
-        content.parse::<Token![,]>()?;
-        let delete_query = content.parse()?;
-        content.parse::<Token![,]>()?;
-        let app_state = content.parse()?;
-        Ok(HttpFindListDeleteDeriveParams(id, find_query, list_query, delete_query, app_state))
+/// The parent declared in the `actix_restful_info` attribute of a model, if any.
+/// The other parameters of the attribute are left to `actix_restful_info` to report.
+fn restful_info_parent(ast: &syn::DeriveInput) -> SynResult<Option<syn::Path>> {
+    let attribute = match ast.attrs.iter().find(|a| is_restful_info(&a.path)) {
+        Some(attribute) => attribute,
+        None => return Ok(None),
+    };
+    let list = match attribute.parse_meta() {
+        Ok(syn::Meta::List(list)) => list,
+        _ => return Ok(None),
+    };
+    let parent = list.nested.iter().find_map(|nested| match nested {
+        syn::NestedMeta::Meta(syn::Meta::NameValue(value)) if value.path.is_ident("parent") => Some(&value.lit),
+        _ => None,
+    });
+    match parent {
+        Some(syn::Lit::Str(parent)) => parent.parse().map(Some).map_err(|err| {
+            syn::Error::new(parent.span(), format!("`parent` expects the path of a model, e.g. \"Project\" : {}", err))
+        }),
+        Some(lit) => Err(syn::Error::new_spanned(lit, "`parent` expects the path of a model as a string, e.g. \"Project\"")),
+        None => Ok(None),
     }
 }
 
+fn is_restful_info(path: &syn::Path) -> bool {
+    path.segments.last().map(|s| s.ident == "actix_restful_info").unwrap_or(false)
+}
+
+/// The derives reading the `parent` of the `actix_restful_info` attribute
+const PARENT_DERIVES: [&str; 4] = ["HttpFindListDelete", "HttpFind", "HttpList", "HttpDelete"];
+
+/// The error of an `actix_restful_info` attribute declaring a parent above one of `PARENT_DERIVES`, which would
+/// not see it
+fn check_parent_derives(ast: &syn::DeriveInput) -> SynResult<()> {
+    for attribute in ast.attrs.iter().filter(|a| a.path.is_ident("derive")) {
+        let derives = match attribute.parse_meta() {
+            Ok(syn::Meta::List(list)) => list.nested,
+            _ => continue,
+        };
+        let derive = derives.iter().find_map(|nested| match nested {
+            syn::NestedMeta::Meta(meta) => meta.path().segments.last().filter(|s| PARENT_DERIVES.iter().any(|d| s.ident == d)),
+            syn::NestedMeta::Lit(_) => None,
+        });
+        if let Some(derive) = derive {
+            return Err(syn::Error::new_spanned(
+                derive,
+                format!("`{}` does not see the `parent` of `actix_restful_info`, place the attribute after `#[derive(..)]`", derive.ident),
+            ));
+        }
+    }
+    Ok(())
+}
 
 #[derive(Debug, FromMeta)]
 struct RestfulInfo {
     pub scope: String,
     pub path: String,
+    #[darling(default)]
+    pub delete_no_content: bool,
+    #[darling(default)]
+    pub parent: Option<syn::Path>,
+    #[darling(default)]
+    pub id_pattern: Option<String>,
+    #[darling(default)]
+    pub id_field: Option<String>,
+    #[darling(default)]
+    pub body_limit: Option<usize>,
+    #[darling(default)]
+    pub filter: Option<String>,
+    #[darling(default)]
+    pub sort: Option<String>,
 }
 
 impl ToTokens for RestfulInfo {
-    fn to_tokens(&self, _tokens: &mut proc_macro2::TokenStream) {
-        ()
-    }
+    fn to_tokens(&self, _tokens: &mut proc_macro2::TokenStream) {}
 }
 
 #[proc_macro_attribute]
 pub fn actix_restful_info(args: proc_macro::TokenStream, input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let attrs_args = parse_macro_input!(args as AttributeArgs);
-    let ast: syn::DeriveInput = syn::parse(input.clone()).unwrap();
+    let item = input.clone();
+    let ast = parse_macro_input!(item as syn::DeriveInput);
 
+    // the errors are reported along with the untouched item, to not add errors about its missing declaration
     let args_tokens = match RestfulInfo::from_list(&attrs_args) {
+        Ok(_) if !ast.generics.params.is_empty() => {
+            let err = syn::Error::new_spanned(&ast.generics, "`actix_restful_info` does not support generic types");
+            return with_item(err.to_compile_error(), input);
+        }
         Ok(v) => v,
-        Err(e) => { return proc_macro::TokenStream::from(e.write_errors()); }
+        Err(e) => { return with_item(e.write_errors(), input); }
+    };
+    let parent_derives = match args_tokens.parent {
+        Some(_) => check_parent_derives(&ast).err().map(|err| err.to_compile_error()),
+        None => None,
+    };
+    let filterable = filter::impl_filterable(&ast, args_tokens.filter.as_deref());
+    let sortable = sort::impl_sortable(&ast, args_tokens.sort.as_deref());
+    let identifiable = impl_identifiable(&ast, args_tokens.id_field.as_deref());
+    let (filterable, sortable, identifiable) = match (filterable, sortable, identifiable) {
+        (Ok(filterable), Ok(sortable), Ok(identifiable)) => (filterable, sortable, identifiable),
+        (Err(err), _, _) | (_, Err(err), _) | (_, _, Err(err)) => return with_item(err.to_compile_error(), input),
+    };
+    let path_info = gen_restful_path_info(&ast, args_tokens);
+    with_item(quote! { #parent_derives #path_info #identifiable #filterable #sortable }, input)
+}
+
+/// The `RestfulPathInfo` implementation of the model `name`
+fn gen_restful_path_info(ast: &syn::DeriveInput, info: RestfulInfo) -> proc_macro2::TokenStream {
+    let name = &ast.ident;
+    let path = info.path;
+    let scope = info.scope;
+    let delete_no_content = info.delete_no_content;
+    let route_path = match info.parent {
+        Some(parent) => quote! {
+            fn route_path() -> String {
+                format!(
+                    "{}/{{{}}}/{}",
+                    <#parent as actix_restful::RestfulPathInfo>::route_path(),
+                    <#parent as actix_restful::RestfulPathInfo>::parent_id_param(),
+                    #path
+                )
+            }
+        },
+        None => quote! {},
     };
-    let name  = ast.ident;
-    let path =args_tokens.path;
-    let scope = args_tokens.scope;
-    let gen = quote! {
-        impl RestfulPathInfo for #name {
+    let id_pattern = match info.id_pattern {
+        Some(id_pattern) => quote! {
+            fn id_pattern() -> String {
+                String::from(#id_pattern)
+            }
+        },
+        None => quote! {},
+    };
+    let body_limit = match info.body_limit {
+        Some(body_limit) => quote! {
+            fn body_limit() -> usize {
+                #body_limit
+            }
+        },
+        None => quote! {},
+    };
+    // the fields which can be picked with `?fields=`, by their serialized names, any field for the structs
+    // without named fields, or flattening the fields of another struct
+    let field_names: Vec<String> = match &ast.data {
+        syn::Data::Struct(syn::DataStruct { fields: syn::Fields::Named(fields), .. }) => {
+            let flattened = fields.named.iter().any(rename::is_flattened);
+            let names = fields.named.iter().filter_map(|field| rename::serialized_name(ast, field));
+            if flattened { Vec::new() } else { names.collect() }
+        }
+        _ => Vec::new(),
+    };
+    quote! {
+        impl actix_restful::RestfulPathInfo for #name {
             fn path() -> String  {
                 let p = #path;
                 let p = p.to_string();
@@ -100,144 +460,501 @@ pub fn actix_restful_info(args: proc_macro::TokenStream, input: proc_macro::Toke
                 let p = #scope;
                 p
             }
+            fn delete_no_content() -> bool {
+                #delete_no_content
+            }
+            #route_path
+            #id_pattern
+            #body_limit
+            fn field_names() -> &'static [&'static str] {
+                &[#(#field_names),*]
+            }
         }
-    };
-    let mut out:proc_macro::TokenStream = gen.into();
-    out.extend::<proc_macro::TokenStream>(input);
+    }
+}
+
+fn with_item(gen: proc_macro2::TokenStream, item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let mut out: proc_macro::TokenStream = gen.into();
+    out.extend::<proc_macro::TokenStream>(item);
     out
 }
 
 #[proc_macro_derive(HttpFindListDelete, attributes(http_find_list_delete))]
 pub fn http_find_list_delete(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let ast = syn::parse(input).unwrap();
-    impl_http_find_list_delete_macro(&ast)
+    let ast = parse_macro_input!(input as syn::DeriveInput);
+    impl_http_find_list_delete_macro(&ast).unwrap_or_else(|err| err.to_compile_error()).into()
 }
 
-fn impl_http_find_list_delete_macro(ast: &syn::DeriveInput) -> proc_macro::TokenStream {
-    let attribute = ast.attrs.iter().filter(
-        |a| a.path.segments.len() == 1 && a.path.segments[0].ident == "http_find_list_delete"
-    ).nth(0).expect("http_find_list_delete attribute required for deriving HttpFindListDelete!");
+fn impl_http_find_list_delete_macro(ast: &syn::DeriveInput) -> SynResult<proc_macro2::TokenStream> {
+    let mut params = derive_params(
+        ast,
+        "HttpFindListDelete",
+        "http_find_list_delete",
+        ["id", "find_query", "list_query", "delete_query", "state"],
+        false,
+    )?;
+    let traits = ModelTraits { parent: restful_info_parent(ast)?, compat: params.compat };
+    let name = &ast.ident;
+    let id = params.get("id", traits.assoc_type(name, "Findable", "Id", None))?;
+    let find_query = params.get("find_query", traits.assoc_type(name, "Findable", "FindQuery", no_query()))?;
+    let list_query = params.get("list_query", traits.assoc_type(name, "Listable", "ListQuery", no_query()))?;
+    let delete_query = params.get("delete_query", traits.assoc_type(name, "Deletable", "DeleteQuery", no_query()))?;
+    let app_state = params.get("state", traits.assoc_type(name, "Findable", "State", no_state()))?;
+    let find = gen_http_find(name, &id, &find_query, &app_state, &traits);
+    let list = gen_http_list(name, &list_query, &app_state, &traits);
+    let delete = gen_http_delete(name, &id, &delete_query, &find_query, &app_state, &traits);
+    Ok(quote! {
+        #find
+        #list
+        #delete
+    })
+}
 
-    let parameter: HttpFindListDeleteDeriveParams = syn::parse2(attribute.tokens.clone()).expect("Invalid http_find_list_delete attribute!");
-    let HttpFindListDeleteDeriveParams(id, find_query, list_query, delete_query, app_state) = parameter;
+#[proc_macro_derive(HttpFind, attributes(http_find))]
+pub fn http_find(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast = parse_macro_input!(input as syn::DeriveInput);
+    impl_http_find_macro(&ast).unwrap_or_else(|err| err.to_compile_error()).into()
+}
 
+fn impl_http_find_macro(ast: &syn::DeriveInput) -> SynResult<proc_macro2::TokenStream> {
+    let mut params = derive_params(ast, "HttpFind", "http_find", ["id", "query", "state"], false)?;
+    let traits = ModelTraits { parent: restful_info_parent(ast)?, compat: params.compat };
     let name = &ast.ident;
-    let gen = quote! {
-        #[derive(Deserialize)]
-        struct ActixRestfulPath {
-            id: #id
-        }
-        #[async_trait]
-        impl HttpFindListDelete<ActixRestfulPath, #find_query, #list_query, #delete_query, #app_state> for #name {
-            async fn http_list(
-                query: actix_web::web::Query<#list_query>,
+    let id = params.get("id", traits.assoc_type(name, "Findable", "Id", None))?;
+    let query = params.get("query", traits.assoc_type(name, "Findable", "FindQuery", no_query()))?;
+    let app_state = params.get("state", traits.assoc_type(name, "Findable", "State", no_state()))?;
+    Ok(gen_http_find(name, &id, &query, &app_state, &traits))
+}
+
+/// The `HttpFind` implementation of the model `name`, along with its `ParentLookup` one
+fn gen_http_find(
+    name: &syn::Ident,
+    id: &syn::Type,
+    find_query: &syn::Type,
+    app_state: &syn::Type,
+    traits: &ModelTraits,
+) -> proc_macro2::TokenStream {
+    let (lookup, parent_id, ctx) = parent_lookup(traits, app_state);
+    let parent_lookup_of_lookup = match &traits.parent {
+        Some(parent) => quote! {
+            let parent_id = <#parent as actix_restful::ParentLookup<#app_state>>::lookup(req, state, ctx).await?;
+        },
+        None => quote! {},
+    };
+    let find = traits.call(name, "Findable", "find");
+    // the context of the lookup is only read by the lookup of the parent with `compat`
+    let (lookup_ctx, ctx_param) = if traits.compat {
+        (quote! {}, if traits.parent.is_some() { quote! { ctx } } else { quote! { _ctx } })
+    } else {
+        (quote! { , ctx }, quote! { ctx })
+    };
+    quote! {
+        #[actix_restful::async_trait(?Send)]
+        impl actix_restful::HttpFind<actix_restful::IdPath<#name, #id>, #find_query, #app_state> for #name {
+            async fn http_find(
+                req: actix_web::HttpRequest,
+                info: actix_restful::IdPath<#name, #id>,
+                query: actix_web::web::Query<#find_query>,
                 state: actix_web::web::Data<#app_state>
-            ) -> Result<actix_web::HttpResponse, actix_web::HttpResponse>{
+            ) -> std::result::Result<actix_web::HttpResponse, actix_web::HttpResponse> {
                 let params = query.into_inner();
-                let result = #name::list(&params, &state).await;
+                let fields = match actix_restful::Fields::from_req(&req, <#name as actix_restful::RestfulPathInfo>::field_names()) {
+                    Ok(fields) => fields,
+                    Err(err) => return Err(err.to_response(&req))
+                };
+                #lookup
+                let result = #find(#parent_id info.id, &params, &state #ctx).await;
                 match result {
-                    Ok(res) => Ok(actix_web::HttpResponse::Ok().body(serde_json::json!(res))),
-                    Err(err) => Err(actix_web::HttpResponse::InternalServerError().body(err.to_string()))
+                    Ok(res) => Ok(actix_restful::projected_response(&req, &fields, &res)),
+                    Err(err) => Err(actix_restful::RestError::from(err).to_response(&req))
                 }
             }
-            async fn http_find(
-                info: actix_web::web::Path<ActixRestfulPath>,
-                query: actix_web::web::Query<#find_query>,
+        }
+        impl actix_restful::FindRoute for #name {
+            fn find_route() -> actix_web::Route {
+                actix_web::web::get().to(<#name as actix_restful::HttpFind<actix_restful::IdPath<#name, #id>, #find_query, #app_state>>::http_find)
+            }
+        }
+        // the bounds hold the requirements of a parent back until the model is named in the `parent` of another one
+        #[actix_restful::async_trait(?Send)]
+        impl actix_restful::ParentLookup<#app_state> for #name
+        where
+            for<'lookup> #find_query: std::default::Default,
+            for<'lookup> #id: std::clone::Clone,
+        {
+            type Id = #id;
+            async fn lookup(req: &actix_web::HttpRequest, state: &#app_state, #ctx_param: &actix_restful::RequestContext) -> std::result::Result<#id, actix_restful::RestError> {
+                #parent_lookup_of_lookup
+                let id: #id = actix_restful::path_param(req, &<#name as actix_restful::RestfulPathInfo>::parent_id_param())?;
+                let find_params: #find_query = std::default::Default::default();
+                match #find(#parent_id id.clone(), &find_params, state #lookup_ctx).await {
+                    Ok(_) => Ok(id),
+                    Err(err) => Err(actix_restful::RestError::from(err))
+                }
+            }
+        }
+    }
+}
+
+#[proc_macro_derive(HttpList, attributes(http_list))]
+pub fn http_list(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast = parse_macro_input!(input as syn::DeriveInput);
+    impl_http_list_macro(&ast).unwrap_or_else(|err| err.to_compile_error()).into()
+}
+
+fn impl_http_list_macro(ast: &syn::DeriveInput) -> SynResult<proc_macro2::TokenStream> {
+    let mut params = derive_params(ast, "HttpList", "http_list", ["query", "state"], false)?;
+    let traits = ModelTraits { parent: restful_info_parent(ast)?, compat: params.compat };
+    let name = &ast.ident;
+    let query = params.get("query", traits.assoc_type(name, "Listable", "ListQuery", no_query()))?;
+    let app_state = params.get("state", traits.assoc_type(name, "Listable", "State", no_state()))?;
+    Ok(gen_http_list(name, &query, &app_state, &traits))
+}
+
+/// The `HttpList` implementation of the model `name`
+fn gen_http_list(
+    name: &syn::Ident,
+    list_query: &syn::Type,
+    app_state: &syn::Type,
+    traits: &ModelTraits,
+) -> proc_macro2::TokenStream {
+    let (lookup, parent_id, ctx) = parent_lookup(traits, app_state);
+    let list = traits.call(name, "Listable", "list");
+    let uses_cursors = traits.assoc_type(name, "Listable", "ListResult", None).map(|list_result| {
+        quote! {
+            fn uses_cursors() -> bool {
+                use actix_restful::{CursorListingKind as _, PlainListingKind as _};
+                (&actix_restful::ListingKind::<#list_result>(std::marker::PhantomData)).uses_cursors()
+            }
+        }
+    });
+    quote! {
+        #[actix_restful::async_trait(?Send)]
+        impl actix_restful::HttpList<#list_query, #app_state> for #name {
+            async fn http_list(
+                req: actix_web::HttpRequest,
+                query: actix_web::web::Query<#list_query>,
                 state: actix_web::web::Data<#app_state>
-            ) -> Result<actix_web::HttpResponse, actix_web::HttpResponse> {
+            ) -> std::result::Result<actix_web::HttpResponse, actix_web::HttpResponse>{
                 let params = query.into_inner();
-                let result = #name::find(info.id.into(), &params, &state).await;
+                let fields = match actix_restful::Fields::from_req(&req, <#name as actix_restful::RestfulPathInfo>::field_names()) {
+                    Ok(fields) => fields,
+                    Err(err) => return Err(err.to_response(&req))
+                };
+                #lookup
+                let result = #list(#parent_id &params, &state #ctx).await;
                 match result {
-                    Ok(res) => Ok(actix_web::HttpResponse::Ok().body(serde_json::json!(res))),
-                    Err(err) => Err(actix_web::HttpResponse::NotFound().body("ENTITY_NOT_FOUND"))
+                    Ok(res) => {
+                        use actix_restful::{PaginatedListing as _, PlainListing as _};
+                        Ok((&actix_restful::Listing(&res)).list_response(&req, &fields))
+                    }
+                    Err(err) => Err(actix_restful::RestError::from(err).to_response(&req))
                 }
             }
+        }
+        impl actix_restful::ListRoute for #name {
+            fn list_route() -> actix_web::Route {
+                actix_web::web::get().to(<#name as actix_restful::HttpList<#list_query, #app_state>>::http_list)
+            }
+            #uses_cursors
+        }
+    }
+}
+
+#[proc_macro_derive(HttpDelete, attributes(http_delete))]
+pub fn http_delete(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast = parse_macro_input!(input as syn::DeriveInput);
+    impl_http_delete_macro(&ast).unwrap_or_else(|err| err.to_compile_error()).into()
+}
+
+fn impl_http_delete_macro(ast: &syn::DeriveInput) -> SynResult<proc_macro2::TokenStream> {
+    let mut params = derive_params(ast, "HttpDelete", "http_delete", ["id", "query", "find_query", "state"], false)?;
+    let traits = ModelTraits { parent: restful_info_parent(ast)?, compat: params.compat };
+    let name = &ast.ident;
+    let id = params.get("id", traits.assoc_type(name, "Findable", "Id", None))?;
+    let query = params.get("query", traits.assoc_type(name, "Deletable", "DeleteQuery", no_query()))?;
+    let find_query = params.get("find_query", traits.assoc_type(name, "Findable", "FindQuery", no_query()))?;
+    let app_state = params.get("state", traits.assoc_type(name, "Findable", "State", no_state()))?;
+    Ok(gen_http_delete(name, &id, &query, &find_query, &app_state, &traits))
+}
+
+/// The `HttpDelete` implementation of the model `name`
+fn gen_http_delete(
+    name: &syn::Ident,
+    id: &syn::Type,
+    delete_query: &syn::Type,
+    find_query: &syn::Type,
+    app_state: &syn::Type,
+    traits: &ModelTraits,
+) -> proc_macro2::TokenStream {
+    let (lookup, parent_id, ctx) = parent_lookup(traits, app_state);
+    let find = traits.call(name, "Findable", "find");
+    let delete = traits.call(name, "Deletable", "delete");
+    quote! {
+        #[actix_restful::async_trait(?Send)]
+        impl actix_restful::HttpDelete<actix_restful::IdPath<#name, #id>, #delete_query, #app_state> for #name {
             async fn http_delete(
-                info: actix_web::web::Path<ActixRestfulPath>,
+                req: actix_web::HttpRequest,
+                info: actix_restful::IdPath<#name, #id>,
                 query: actix_web::web::Query<#delete_query>,
                 state: actix_web::web::Data<#app_state>
-            ) -> Result<actix_web::HttpResponse, actix_web::HttpResponse> {
+            ) -> std::result::Result<actix_web::HttpResponse, actix_web::HttpResponse> {
                 let params = query.into_inner();
-                let find_params: #find_query = Default::default();
-                let result = #name::find(info.id.into(), &find_params, &state).await;
+                let find_params: #find_query = std::default::Default::default();
+                #lookup
+                let result = #find(#parent_id info.id, &find_params, &state #ctx).await;
 
                 match result {
                     Ok(entity) => {
-                        match entity.delete(&params, &state).await {
-                            Ok(e) => Ok(actix_web::HttpResponse::Ok().body(serde_json::json!(e))),
-                            Err(err) => Err(actix_web::HttpResponse::InternalServerError().body(err.to_string()))
+                        match #delete(*entity, #parent_id &params, &state #ctx).await {
+                            Ok(_) if <#name as actix_restful::RestfulPathInfo>::delete_no_content() => Ok(actix_web::HttpResponse::NoContent().finish()),
+                            Ok(e) => Ok(actix_restful::json_response(&req, actix_web::http::StatusCode::OK, &e)),
+                            Err(err) => Err(actix_restful::RestError::from(err).to_response(&req))
                         }
                     }
-                    Err(err) => Err(actix_web::HttpResponse::NotFound().body("ENTITY_NOT_FOUND"))
+                    Err(err) => Err(actix_restful::RestError::from(err).to_response(&req))
                 }
             }
         }
-    };
-    gen.into()
+        impl actix_restful::DeleteRoute for #name {
+            fn delete_route() -> actix_web::Route {
+                actix_web::web::delete().to(<#name as actix_restful::HttpDelete<actix_restful::IdPath<#name, #id>, #delete_query, #app_state>>::http_delete)
+            }
+        }
+    }
 }
 
-struct HttpUpdateDeriveParams (syn::Ident, syn::Ident, syn::Ident, syn::Ident, syn::Ident);
-impl syn::parse::Parse for HttpUpdateDeriveParams {
-    fn parse(input: syn::parse::ParseStream) -> SynResult<Self> {
-        let content;
-        syn::parenthesized!(content in input);
-        let id = content.parse()?;
-        content.parse::<syn::Token![,]>()?;
-        let query = content.parse()?;
-        content.parse::<syn::Token![,]>()?;
-        let output = content.parse()?;
-        content.parse::<syn::Token![,]>()?;
-        let find_query = content.parse()?;
-        content.parse::<Token![,]>()?;
-        let app_state = content.parse()?;
-        Ok(HttpUpdateDeriveParams(id, query, output, find_query, app_state))
-    }
+/// Implements `Identifiable` from the field named by the `id_field` parameter, or from the `id` field of the entity
+/// if it has one
+pub(crate) fn impl_identifiable(ast: &syn::DeriveInput, id_field: Option<&str>) -> SynResult<proc_macro2::TokenStream> {
+    let field = match id_field {
+        Some(id_field) => {
+            let fields = filter::named_fields(ast, "id_field")?;
+            filter::find_field(ast, fields, id_field, "id_field")?.ident.as_ref()
+        }
+        None => match &ast.data {
+            syn::Data::Struct(data) => data.fields.iter().filter_map(|f| f.ident.as_ref()).find(|i| *i == "id"),
+            _ => None,
+        },
+    };
+    let field = match field {
+        Some(field) => field,
+        None => return Ok(quote! {}),
+    };
+    let name = &ast.ident;
+    Ok(quote! {
+        impl actix_restful::Identifiable for #name {
+            fn resource_id(&self) -> String {
+                self.#field.to_string()
+            }
+        }
+    })
 }
 
 #[proc_macro_derive(HttpUpdate, attributes(http_update))]
 pub fn http_update(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let ast = syn::parse(input).unwrap();
-    impl_http_update_macro(&ast)
+    let ast = parse_macro_input!(input as syn::DeriveInput);
+    impl_http_update_macro(&ast).unwrap_or_else(|err| err.to_compile_error()).into()
 }
 
-fn impl_http_update_macro(ast: &syn::DeriveInput) -> proc_macro::TokenStream {
-    let attribute = ast.attrs.iter().filter(
-        |a| a.path.segments.len() == 1 && a.path.segments[0].ident == "http_update"
-    ).nth(0).expect("http_update attribute required for deriving HttpUpdate!");
+/// The parameters of `http_update` and `http_patch`
+const UPDATE_PARAMS: [&str; 5] = ["id", "query", "model", "find_query", "state"];
 
-    let parameter: HttpUpdateDeriveParams = syn::parse2(attribute.tokens.clone()).expect("Invalid http_update attribute!");
-    let HttpUpdateDeriveParams(id, query, output, find_query, app_state) = parameter;
+/// The types of the handler of the payload `name` updating a model, whose model trait is `payload_trait`
+fn update_params(
+    name: &syn::Ident,
+    params: &mut DeriveParams<5>,
+    traits: &ModelTraits,
+    payload_trait: &str,
+) -> SynResult<[syn::Type; 5]> {
+    let output = params.get("model", traits.assoc_type(name, payload_trait, "Model", None))?;
+    let id = params.get("id", traits.assoc_type(&output, "Findable", "Id", None))?;
+    let query = params.get("query", traits.assoc_type(name, payload_trait, "Query", no_query()))?;
+    let find_query = params.get("find_query", traits.assoc_type(&output, "Findable", "FindQuery", no_query()))?;
+    let app_state = params.get("state", traits.assoc_type(name, payload_trait, "State", no_state()))?;
+    Ok([id, query, output, find_query, app_state])
+}
 
-    let name = &ast.ident;
-    let gen = quote! {
-        #[derive(Deserialize)]
-        struct ActixRestfulUpdatePath {
-            id: #id
-        }
-        #[async_trait]
-        impl HttpUpdate<ActixRestfulUpdatePath, #query, #app_state> for #name {
+fn impl_http_update_macro(ast: &syn::DeriveInput) -> SynResult<proc_macro2::TokenStream> {
+    let mut params = derive_params(ast, "HttpUpdate", "http_update", UPDATE_PARAMS, true)?;
+    let traits = ModelTraits { parent: params.parent.clone(), compat: params.compat };
+    let [id, query, output, find_query, app_state] = update_params(&ast.ident, &mut params, &traits, "UpdatableModel")?;
+    Ok(gen_http_update(&ast.ident, &id, &query, &output, &find_query, &app_state, &traits))
+}
+
+/// The `HttpUpdate` implementation of the payload `name`, updating the model `output`
+fn gen_http_update(
+    name: &impl ToTokens,
+    id: &syn::Type,
+    query: &syn::Type,
+    output: &syn::Type,
+    find_query: &syn::Type,
+    app_state: &syn::Type,
+    traits: &ModelTraits,
+) -> proc_macro2::TokenStream {
+    let (lookup, parent_id, ctx) = parent_lookup(traits, app_state);
+    let find = traits.call(output, "Findable", "find");
+    let validate = validate_payload(quote! { to_update });
+    let update = traits.call(name, "UpdatableModel", "update");
+    // the generic trait of `compat` receives neither the id nor the current entity
+    let update = if traits.compat {
+        quote! { #update(to_update, #parent_id &params, &state) }
+    } else {
+        quote! { #update(to_update, #parent_id info.id, *entity, &params, &state #ctx) }
+    };
+    quote! {
+        #[actix_restful::async_trait(?Send)]
+        impl actix_restful::HttpUpdate<actix_restful::IdPath<#output, #id>, #query, #app_state> for #name {
             async fn http_update(
-                info: actix_web::web::Path<ActixRestfulUpdatePath>,
-                payload: actix_web::web::Json<Box<#name>>,
+                req: actix_web::HttpRequest,
+                info: actix_restful::IdPath<#output, #id>,
+                payload: actix_restful::Body,
                 query: actix_web::web::Query<#query>,
                 state: actix_web::web::Data<#app_state>
-            ) -> Result<actix_web::HttpResponse, actix_web::HttpResponse> {
-                let to_update = payload.into_inner();
+            ) -> std::result::Result<actix_web::HttpResponse, actix_web::HttpResponse> {
                 let params = query.into_inner();
-                let find_params: #find_query = Default::default();
-                let result = #output::find(info.id.into(), &find_params, &state).await;
+                let find_params: #find_query = std::default::Default::default();
+                #lookup
+                let result = #find(#parent_id info.id.clone(), &find_params, &state #ctx).await;
 
                 match result {
                     Ok(entity) => {
-                        match to_update.update(&params, &state).await {
-                            Ok(e) => Ok(actix_web::HttpResponse::Ok().body(serde_json::json!(e))),
-                            Err(err) => Err(actix_web::HttpResponse::InternalServerError().body(err.to_string()))
+                        let to_update: #name = match actix_restful::update_payload(&req, &payload, &info.id) {
+                            Ok(to_update) => to_update,
+                            Err(err) => return Err(err.to_response(&req))
+                        };
+                        #validate
+                        match #update.await {
+                            Ok(e) => Ok(actix_restful::json_response(&req, actix_web::http::StatusCode::OK, &e)),
+                            Err(err) => Err(actix_restful::RestError::from(err).to_response(&req))
                         }
                     }
-                    Err(err) => Err(actix_web::HttpResponse::NotFound().body("ENTITY_NOT_FOUND"))
+                    Err(err) => Err(actix_restful::RestError::from(err).to_response(&req))
                 }
             }
         }
+        impl actix_restful::UpdateRoute for #name {
+            fn update_route() -> actix_web::Route {
+                actix_web::web::put().to(<#name as actix_restful::HttpUpdate<actix_restful::IdPath<#output, #id>, #query, #app_state>>::http_update)
+            }
+        }
+    }
+}
+
+#[proc_macro_derive(HttpPatch, attributes(http_patch))]
+pub fn http_patch(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast = parse_macro_input!(input as syn::DeriveInput);
+    impl_http_patch_macro(&ast).unwrap_or_else(|err| err.to_compile_error()).into()
+}
+
+fn impl_http_patch_macro(ast: &syn::DeriveInput) -> SynResult<proc_macro2::TokenStream> {
+    // http_patch takes the same parameters as http_update
+    let mut params = derive_params(ast, "HttpPatch", "http_patch", UPDATE_PARAMS, true)?;
+    let traits = ModelTraits { parent: params.parent.clone(), compat: params.compat };
+    let [id, query, output, find_query, app_state] = update_params(&ast.ident, &mut params, &traits, "PatchableModel")?;
+    Ok(gen_http_patch(&ast.ident, &id, &query, &output, &find_query, &app_state, &traits))
+}
+
+/// The `HttpPatch` implementation of the payload `name`, patching the model `output`
+fn gen_http_patch(
+    name: &impl ToTokens,
+    id: &syn::Type,
+    query: &syn::Type,
+    output: &syn::Type,
+    find_query: &syn::Type,
+    app_state: &syn::Type,
+    traits: &ModelTraits,
+) -> proc_macro2::TokenStream {
+    let (lookup, parent_id, ctx) = parent_lookup(traits, app_state);
+    let find = traits.call(output, "Findable", "find");
+    let validate = validate_payload(quote! { to_patch });
+    let patch = traits.call(name, "PatchableModel", "patch");
+    // the generic trait of `compat` does not receive the id
+    let patch = if traits.compat {
+        quote! { #patch(to_patch, #parent_id &params, &state) }
+    } else {
+        quote! { #patch(to_patch, #parent_id info.id, &params, &state #ctx) }
     };
-    gen.into()
+    quote! {
+        #[actix_restful::async_trait(?Send)]
+        impl actix_restful::HttpPatch<actix_restful::IdPath<#output, #id>, #query, #app_state> for #name {
+            async fn http_patch(
+                req: actix_web::HttpRequest,
+                info: actix_restful::IdPath<#output, #id>,
+                payload: actix_restful::Body,
+                query: actix_web::web::Query<#query>,
+                state: actix_web::web::Data<#app_state>
+            ) -> std::result::Result<actix_web::HttpResponse, actix_web::HttpResponse> {
+                let params = query.into_inner();
+                let find_params: #find_query = std::default::Default::default();
+                #lookup
+                let result = #find(#parent_id info.id.clone(), &find_params, &state #ctx).await;
+
+                match result {
+                    Ok(entity) => {
+                        let to_patch: #name = match actix_restful::apply_patch(&req, &entity, &payload, &info.id) {
+                            Ok(merged) => merged,
+                            Err(err) => return Err(err.to_response(&req))
+                        };
+                        #validate
+                        match #patch.await {
+                            Ok(e) => Ok(actix_restful::json_response(&req, actix_web::http::StatusCode::OK, &e)),
+                            Err(err) => Err(actix_restful::RestError::from(err).to_response(&req))
+                        }
+                    }
+                    Err(err) => Err(actix_restful::RestError::from(err).to_response(&req))
+                }
+            }
+        }
+        impl actix_restful::PatchRoute for #name {
+            fn patch_route() -> actix_web::Route {
+                actix_web::web::patch().to(<#name as actix_restful::HttpPatch<actix_restful::IdPath<#output, #id>, #query, #app_state>>::http_patch)
+            }
+        }
+    }
+}
+
+/// Declares a model and its payloads in a single attribute, and generates all their handlers :
+///
+/// ```ignore
+/// #[derive(Default, Serialize, Deserialize, Restful)]
+/// #[restful(scope = "/v1", path = "item", create = NewItem, update = UpdatableItem)]
+/// struct Item {
+///     id: Id,
+/// }
+///
+/// actix_web::web::scope(Item::scope()).configure(<Item as RestfulRoutes>::configure)
+/// ```
+///
+/// The parameters are those of `actix_restful_info`, `http_find_list_delete`, `http_create`, `http_update`
+/// and `http_patch`. The `create`, `update` and `patch` payloads, each with its `*_query` type, are optional.
+/// The model answers find, list and delete, or only the operations among them given as flags :
+/// `#[restful(scope = "/v1", path = "event", find, list, create = NewEvent)]`.
+#[proc_macro_derive(Restful, attributes(restful))]
+pub fn restful(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast = parse_macro_input!(input as syn::DeriveInput);
+    restful::impl_restful_macro(&ast).unwrap_or_else(|err| err.to_compile_error()).into()
+}
+
+/// Implements `actix_restful::Validate` from the rules of the fields, checked by the generated handlers
+/// before `save`, `update` or `patch` :
+///
+/// ```ignore
+/// #[derive(Serialize, Deserialize, HttpCreate, Validate)]
+/// struct NewItem {
+///     #[restful(length(min = 1, max = 200))]
+///     content: String,
+///     #[restful(email)]
+///     contact: Option<String>,
+///     #[restful(range(min = 0, max = 5))]
+///     priority: u8,
+/// }
+/// ```
+///
+/// The rules are `length(min = .., max = ..)`, `email`, `range(min = .., max = ..)` and `custom = "function"`,
+/// see `actix_restful::Validate`.
+#[proc_macro_derive(Validate, attributes(restful))]
+pub fn validate(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast = parse_macro_input!(input as syn::DeriveInput);
+    validate::impl_validate_macro(&ast).unwrap_or_else(|err| err.to_compile_error()).into()
 }
@@ -0,0 +1,87 @@
+use syn::ext::IdentExt;
+
+/// The `serialize` name of a `rename` or `rename_all` serde attribute : `rename = ".."` or `rename(serialize = "..")`
+fn serialize_name(meta: &syn::Meta) -> Option<String> {
+    match meta {
+        syn::Meta::NameValue(syn::MetaNameValue { lit: syn::Lit::Str(name), .. }) => Some(name.value()),
+        syn::Meta::List(list) => list.nested.iter().find_map(|nested| match nested {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(value)) if value.path.is_ident("serialize") => match &value.lit {
+                syn::Lit::Str(name) => Some(name.value()),
+                _ => None,
+            },
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+/// The items of the `#[serde(..)]` attributes, the malformed ones being left to serde to report
+fn serde_metas(attrs: &[syn::Attribute]) -> Vec<syn::Meta> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("serde"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::List(list)) => Some(list.nested.into_iter()),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|nested| match nested {
+            syn::NestedMeta::Meta(meta) => Some(meta),
+            syn::NestedMeta::Lit(_) => None,
+        })
+        .collect()
+}
+
+/// The name of a snake case field with the `rename_all` rule of serde, unchanged for an unknown rule
+fn apply_rule(rule: &str, name: &str) -> String {
+    let pascal = || {
+        name.split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                chars.next().map(|first| first.to_ascii_uppercase().to_string() + chars.as_str()).unwrap_or_default()
+            })
+            .collect::<String>()
+    };
+    match rule {
+        "UPPERCASE" | "SCREAMING_SNAKE_CASE" => name.to_ascii_uppercase(),
+        "PascalCase" => pascal(),
+        "camelCase" => {
+            let pascal = pascal();
+            let mut chars = pascal.chars();
+            chars.next().map(|first| first.to_ascii_lowercase().to_string() + chars.as_str()).unwrap_or_default()
+        }
+        "kebab-case" => name.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => name.to_ascii_uppercase().replace('_', "-"),
+        _ => name.to_string(),
+    }
+}
+
+/// Whether serde flattens the fields of the field in the model
+pub(crate) fn is_flattened(field: &syn::Field) -> bool {
+    serde_metas(&field.attrs).iter().any(|meta| meta.path().is_ident("flatten"))
+}
+
+/// The name of a field of the model in its JSON serialization, read from the `rename` attribute of the field or from
+/// the `rename_all` attribute of the model, `r#type` being serialized as `type`.
+/// `None` when serde skips the field, or flattens its fields in the model.
+pub(crate) fn serialized_name(ast: &syn::DeriveInput, field: &syn::Field) -> Option<String> {
+    let mut rename = None;
+    for meta in serde_metas(&field.attrs) {
+        let path = meta.path();
+        if path.is_ident("skip") || path.is_ident("skip_serializing") || path.is_ident("flatten") {
+            return None;
+        }
+        if path.is_ident("rename") {
+            rename = serialize_name(&meta).or(rename);
+        }
+    }
+    let ident = field.ident.as_ref()?.unraw().to_string();
+    if rename.is_some() {
+        return rename;
+    }
+    let rule = serde_metas(&ast.attrs).iter().filter(|meta| meta.path().is_ident("rename_all")).find_map(serialize_name);
+    Some(match rule {
+        Some(rule) => apply_rule(&rule, &ident),
+        None => ident,
+    })
+}
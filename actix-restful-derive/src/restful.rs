@@ -0,0 +1,270 @@
+use crate::{
+    filter, gen_http_create, gen_http_delete, gen_http_find, gen_http_list, gen_http_patch,
+    gen_http_update, gen_restful_path_info, impl_identifiable, no_query, no_state, sort, ModelTraits, RestfulInfo,
+};
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::{Result as SynResult, Token};
+
+/// The parameters of the `restful` attribute
+const KEYS: [&str; 24] = [
+    "scope",
+    "path",
+    "find",
+    "list",
+    "delete",
+    "id",
+    "id_pattern",
+    "id_field",
+    "state",
+    "parent",
+    "delete_no_content",
+    "body_limit",
+    "filter",
+    "sort",
+    "find_query",
+    "list_query",
+    "delete_query",
+    "create",
+    "create_query",
+    "update",
+    "update_query",
+    "patch",
+    "patch_query",
+    "compat",
+];
+
+/// The value of a parameter of the `restful` attribute : a string, an integer, or a type
+enum Value {
+    Str(syn::LitStr),
+    Int(syn::LitInt),
+    Type(Box<syn::Type>),
+}
+
+/// A parameter of the `restful` attribute : `key = "value"`, `key = 42`, `key = Type`, or a flag
+struct Param {
+    key: syn::Ident,
+    value: Option<Value>,
+}
+
+impl syn::parse::Parse for Param {
+    fn parse(input: syn::parse::ParseStream) -> SynResult<Self> {
+        let key: syn::Ident = input.parse()?;
+        if !KEYS.iter().any(|k| key == k) {
+            return Err(syn::Error::new(
+                key.span(),
+                format!("unknown parameter `{}`, expected one of `{}`", key, KEYS.join("`, `")),
+            ));
+        }
+        if !input.peek(Token![=]) {
+            return Ok(Param { key, value: None });
+        }
+        input.parse::<Token![=]>()?;
+        let value = if input.peek(syn::LitStr) {
+            Value::Str(input.parse()?)
+        } else if input.peek(syn::LitInt) {
+            Value::Int(input.parse()?)
+        } else {
+            Value::Type(Box::new(input.parse()?))
+        };
+        Ok(Param { key, value: Some(value) })
+    }
+}
+
+/// The parameters given to the `restful` attribute, taken one by one
+struct Params<'a> {
+    attribute: &'a syn::Attribute,
+    params: Vec<Param>,
+}
+
+impl<'a> Params<'a> {
+    fn parse(attribute: &'a syn::Attribute) -> SynResult<Self> {
+        let mut params: Vec<Param> = Vec::new();
+        for param in attribute.parse_args_with(Punctuated::<Param, Token![,]>::parse_terminated)? {
+            if params.iter().any(|p| p.key == param.key) {
+                return Err(syn::Error::new(param.key.span(), format!("duplicate parameter `{}`", param.key)));
+            }
+            params.push(param);
+        }
+        Ok(Params { attribute, params })
+    }
+
+    fn take(&mut self, key: &str) -> Option<Param> {
+        let index = self.params.iter().position(|p| p.key == key)?;
+        Some(self.params.remove(index))
+    }
+
+    fn missing(&self, key: &str, usage: &str) -> syn::Error {
+        syn::Error::new_spanned(
+            self.attribute,
+            format!("`#[derive(Restful)]` requires the parameter `{}` : `#[restful({} = {})]`", key, key, usage),
+        )
+    }
+
+    fn string(&mut self, key: &str) -> SynResult<Option<String>> {
+        match self.take(key) {
+            None => Ok(None),
+            Some(Param { value: Some(Value::Str(value)), .. }) => Ok(Some(value.value())),
+            Some(Param { key, .. }) => Err(syn::Error::new(key.span(), format!("expected `{} = \"..\"`", key))),
+        }
+    }
+
+    fn required_string(&mut self, key: &str) -> SynResult<String> {
+        self.string(key)?.ok_or_else(|| self.missing(key, "\"..\""))
+    }
+
+    fn usize(&mut self, key: &str) -> SynResult<Option<usize>> {
+        match self.take(key) {
+            None => Ok(None),
+            Some(Param { value: Some(Value::Int(value)), .. }) => Ok(Some(value.base10_parse()?)),
+            Some(Param { key, .. }) => Err(syn::Error::new(key.span(), format!("expected `{} = 42`", key))),
+        }
+    }
+
+    /// A type, which can be given as a string as in `actix_restful_info`
+    fn ty(&mut self, key: &str) -> SynResult<Option<syn::Type>> {
+        match self.take(key) {
+            None => Ok(None),
+            Some(Param { value: Some(Value::Type(value)), .. }) => Ok(Some(*value)),
+            Some(Param { value: Some(Value::Str(value)), .. }) => Ok(Some(value.parse()?)),
+            Some(Param { key, .. }) => Err(syn::Error::new(key.span(), format!("expected `{} = Type`", key))),
+        }
+    }
+
+    fn ty_or(&mut self, key: &str, default: Option<syn::Type>) -> SynResult<syn::Type> {
+        match self.ty(key)?.or(default) {
+            Some(value) => Ok(value),
+            None => Err(self.missing(key, "Type")),
+        }
+    }
+
+    fn flag(&mut self, key: &str) -> SynResult<bool> {
+        match self.take(key) {
+            None => Ok(false),
+            Some(Param { value: None, .. }) => Ok(true),
+            Some(Param { key, .. }) => Err(syn::Error::new(key.span(), format!("`{}` takes no value", key))),
+        }
+    }
+
+    fn path(&mut self, key: &str) -> SynResult<Option<syn::Path>> {
+        match self.ty(key)? {
+            None => Ok(None),
+            Some(syn::Type::Path(value)) if value.qself.is_none() => Ok(Some(value.path)),
+            Some(value) => Err(syn::Error::new_spanned(value, format!("expected `{} = Model`", key))),
+        }
+    }
+}
+
+/// The query type of an optional payload, its `Query` by default
+fn payload_query(
+    params: &mut Params,
+    traits: &ModelTraits,
+    payload: &Option<syn::Type>,
+    key: &str,
+    payload_trait: &str,
+) -> SynResult<syn::Type> {
+    let default = match payload {
+        Some(payload) => traits.assoc_type(payload, payload_trait, "Query", no_query()),
+        None => no_query(),
+    };
+    params.ty_or(key, default)
+}
+
+pub(crate) fn impl_restful_macro(ast: &syn::DeriveInput) -> SynResult<proc_macro2::TokenStream> {
+    if !ast.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(&ast.generics, "`#[derive(Restful)]` does not support generic types"));
+    }
+    let attribute = ast.attrs.iter().find(|a| a.path.is_ident("restful")).ok_or_else(|| {
+        syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`#[derive(Restful)]` requires the attribute `#[restful(scope = \"..\", path = \"..\")]`",
+        )
+    })?;
+    let mut params = Params::parse(attribute)?;
+
+    let info = RestfulInfo {
+        scope: params.required_string("scope")?,
+        path: params.required_string("path")?,
+        delete_no_content: params.flag("delete_no_content")?,
+        parent: params.path("parent")?,
+        id_pattern: params.string("id_pattern")?,
+        id_field: params.string("id_field")?,
+        body_limit: params.usize("body_limit")?,
+        filter: params.string("filter")?,
+        sort: params.string("sort")?,
+    };
+    let name = &ast.ident;
+    let traits = ModelTraits { parent: info.parent.clone(), compat: params.flag("compat")? };
+    // the model answers find, list and delete unless some of them are picked
+    let (mut find, mut list, mut delete) = (params.flag("find")?, params.flag("list")?, params.flag("delete")?);
+    if !(find || list || delete) {
+        (find, list, delete) = (true, true, true);
+    }
+    let create = params.ty("create")?;
+    let create_query = payload_query(&mut params, &traits, &create, "create_query", "NewModel")?;
+    let update = params.ty("update")?;
+    let update_query = payload_query(&mut params, &traits, &update, "update_query", "UpdatableModel")?;
+    let patch = params.ty("patch")?;
+    let patch_query = payload_query(&mut params, &traits, &patch, "patch_query", "PatchableModel")?;
+
+    // the id, the state and the find query are read from `Findable`, unless the model is only listed
+    let findable = find || delete || update.is_some() || patch.is_some();
+    let state_trait = if findable { "Findable" } else { "Listable" };
+    // a model which is only listed has no id
+    let id = match findable {
+        true => params.ty_or("id", traits.assoc_type(name, "Findable", "Id", None))?,
+        false => params.ty("id")?.unwrap_or_else(|| syn::parse_quote!(())),
+    };
+    let app_state = params.ty_or("state", traits.assoc_type(name, state_trait, "State", no_state()))?;
+    let find_query = params.ty_or("find_query", traits.assoc_type(name, "Findable", "FindQuery", no_query()))?;
+    let list_query = params.ty_or("list_query", traits.assoc_type(name, "Listable", "ListQuery", no_query()))?;
+    let delete_query = params.ty_or("delete_query", traits.assoc_type(name, "Deletable", "DeleteQuery", no_query()))?;
+
+    let filterable = filter::impl_filterable(ast, info.filter.as_deref())?;
+    let sortable = sort::impl_sortable(ast, info.sort.as_deref())?;
+    let identifiable = impl_identifiable(ast, info.id_field.as_deref())?;
+    let path_info = gen_restful_path_info(ast, info);
+    let find_impl = find.then(|| gen_http_find(name, &id, &find_query, &app_state, &traits));
+    let list_impl = list.then(|| gen_http_list(name, &list_query, &app_state, &traits));
+    let delete_impl = delete.then(|| gen_http_delete(name, &id, &delete_query, &find_query, &app_state, &traits));
+    let find_route = find.then(|| quote! { .find() });
+    let list_route = list.then(|| quote! { .list() });
+    let delete_route = delete.then(|| quote! { .delete() });
+    let model: syn::Type = syn::parse_quote!(#name);
+    let create_impl = create.iter().map(|create| gen_http_create(create, &create_query, &app_state, &traits));
+    let update_impl = update
+        .iter()
+        .map(|update| gen_http_update(update, &id, &update_query, &model, &find_query, &app_state, &traits));
+    let patch_impl = patch
+        .iter()
+        .map(|patch| gen_http_patch(patch, &id, &patch_query, &model, &find_query, &app_state, &traits));
+    let create_route = create.iter().map(|create| quote! { .create::<#create>() });
+    let update_route = update.iter().map(|update| quote! { .update::<#update>() });
+    let patch_route = patch.iter().map(|patch| quote! { .patch::<#patch>() });
+
+    Ok(quote! {
+        #path_info
+        #identifiable
+        #filterable
+        #sortable
+        #find_impl
+        #list_impl
+        #delete_impl
+        #(#create_impl)*
+        #(#update_impl)*
+        #(#patch_impl)*
+        impl actix_restful::RestfulRoutes for #name {
+            fn configure(cfg: &mut actix_web::web::ServiceConfig) {
+                let configure = actix_restful::Resource::<#name>::new()
+                    #find_route
+                    #list_route
+                    #delete_route
+                    #(#create_route)*
+                    #(#update_route)*
+                    #(#patch_route)*
+                    .configure();
+                configure(cfg)
+            }
+        }
+    })
+}
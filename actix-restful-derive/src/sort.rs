@@ -0,0 +1,25 @@
+use crate::filter::{field_serialized_name, named_fields};
+use quote::quote;
+use syn::Result as SynResult;
+
+/// The `Sortable` implementation of the model, from the fields listed in its `sort` parameter
+pub(crate) fn impl_sortable(ast: &syn::DeriveInput, sort: Option<&str>) -> SynResult<proc_macro2::TokenStream> {
+    let sort = match sort {
+        Some(sort) => sort,
+        None => return Ok(quote! {}),
+    };
+    let fields = named_fields(ast, "sort")?;
+    let mut sort_fields = Vec::new();
+    for name in sort.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+        let (serialized, _) = field_serialized_name(ast, fields, name, "sort")?;
+        sort_fields.push(serialized);
+    }
+    let name = &ast.ident;
+    Ok(quote! {
+        impl actix_restful::Sortable for #name {
+            fn sort_fields() -> &'static [&'static str] {
+                &[#(#sort_fields),*]
+            }
+        }
+    })
+}
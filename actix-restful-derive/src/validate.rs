@@ -0,0 +1,157 @@
+use crate::rename;
+use darling::FromMeta;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::Result as SynResult;
+
+/// The rules of a field, read from its `restful` attributes
+#[derive(Default, FromMeta)]
+#[darling(default)]
+struct FieldRules {
+    length: Option<LengthRule>,
+    email: bool,
+    range: Option<RangeRule>,
+    custom: Option<syn::Path>,
+}
+
+#[derive(FromMeta)]
+struct LengthRule {
+    #[darling(default)]
+    min: Option<usize>,
+    #[darling(default)]
+    max: Option<usize>,
+}
+
+#[derive(FromMeta)]
+struct RangeRule {
+    #[darling(default)]
+    min: Option<Bound>,
+    #[darling(default)]
+    max: Option<Bound>,
+}
+
+/// A bound of the `range` rule, an integer or a float, given as a string when negative : `min = "-10"`
+struct Bound(f64);
+
+impl FromMeta for Bound {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        value.parse().map(Bound).map_err(|_| darling::Error::unknown_value(value))
+    }
+
+    fn from_value(value: &syn::Lit) -> darling::Result<Self> {
+        match value {
+            syn::Lit::Int(int) => int.base10_parse().map(Bound).map_err(darling::Error::from),
+            syn::Lit::Float(float) => float.base10_parse().map(Bound).map_err(darling::Error::from),
+            syn::Lit::Str(s) => Self::from_string(&s.value()),
+            _ => Err(darling::Error::unexpected_lit_type(value)),
+        }
+        .map_err(|err| err.with_span(value))
+    }
+}
+
+fn optional<T: quote::ToTokens>(value: &Option<T>) -> proc_macro2::TokenStream {
+    match value {
+        Some(value) => quote! { Some(#value) },
+        None => quote! { None },
+    }
+}
+
+/// Whether the type of a field is an `Option`, whose rules are checked on its value
+fn is_option(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(path) if path.qself.is_none() => {
+            path.path.segments.last().is_some_and(|segment| segment.ident == "Option")
+        }
+        _ => false,
+    }
+}
+
+/// The checks of a field, on `value` a reference to it
+fn field_checks(name: &str, rules: &FieldRules) -> proc_macro2::TokenStream {
+    let mut checks = Vec::new();
+    if let Some(length) = &rules.length {
+        let min = optional(&length.min);
+        let max = optional(&length.max);
+        checks.push((quote! { actix_restful::validate_length(value, #min, #max) }, "length"));
+    }
+    if rules.email {
+        checks.push((quote! { actix_restful::validate_email(value) }, "email"));
+    }
+    if let Some(range) = &rules.range {
+        let min = optional(&range.min.as_ref().map(|bound| bound.0));
+        let max = optional(&range.max.as_ref().map(|bound| bound.0));
+        checks.push((quote! { actix_restful::validate_range(*value as f64, #min, #max) }, "range"));
+    }
+    if let Some(custom) = &rules.custom {
+        checks.push((quote! { #custom(value) }, "custom"));
+    }
+    let checks = checks.into_iter().map(|(check, code)| {
+        quote! {
+            if let Err(message) = #check {
+                errors.add(#name, #code, message);
+            }
+        }
+    });
+    quote! { #(#checks)* }
+}
+
+pub(crate) fn impl_validate_macro(ast: &syn::DeriveInput) -> SynResult<proc_macro2::TokenStream> {
+    let fields = match &ast.data {
+        syn::Data::Struct(syn::DataStruct { fields: syn::Fields::Named(fields), .. }) => &fields.named,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &ast.ident,
+                "`#[derive(Validate)]` only supports structs with named fields",
+            ))
+        }
+    };
+    let mut validations = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().expect("named field");
+        let mut rules = FieldRules::default();
+        for attribute in field.attrs.iter().filter(|a| a.path.is_ident("restful")) {
+            let meta = attribute.parse_meta()?;
+            let field_rules = match FieldRules::from_meta(&meta) {
+                Ok(field_rules) => field_rules,
+                // the darling errors are reported as they are, with the span of the faulty rule
+                Err(err) => return Ok(err.with_span(attribute).write_errors()),
+            };
+            rules = FieldRules {
+                length: field_rules.length.or(rules.length),
+                email: field_rules.email || rules.email,
+                range: field_rules.range.or(rules.range),
+                custom: field_rules.custom.or(rules.custom),
+            };
+        }
+        // the errors name the field as the client writes it, `r#type` as `type`
+        let name = rename::serialized_name(ast, field).unwrap_or_else(|| ident.unraw().to_string());
+        let checks = field_checks(&name, &rules);
+        if checks.is_empty() {
+            continue;
+        }
+        validations.push(if is_option(&field.ty) {
+            quote! {
+                if let Some(value) = &self.#ident {
+                    #checks
+                }
+            }
+        } else {
+            quote! {
+                let value = &self.#ident;
+                #checks
+            }
+        });
+    }
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics actix_restful::Validate for #name #ty_generics #where_clause {
+            fn validate(&self) -> std::result::Result<(), actix_restful::ValidationErrors> {
+                #[allow(unused_mut)]
+                let mut errors = actix_restful::ValidationErrors::default();
+                #({ #validations })*
+                errors.into_result()
+            }
+        }
+    })
+}
@@ -0,0 +1,102 @@
+// the generic traits of `compat` and the traits of the crate root, side by side in the same crate
+use actix_restful::compat::{Model, NewModel, PatchableModel, UpdatableModel};
+use actix_restful::{Findable, Listable, NoQuery, RequestContext, RestfulPathInfo};
+use actix_restful_derive::{actix_restful_info, HttpCreate, HttpFind, HttpFindListDelete, HttpList, HttpPatch, HttpUpdate};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+type Id = i64;
+
+#[derive(Serialize, Deserialize, HttpFindListDelete)]
+#[http_find_list_delete(compat, id = Id)]
+#[actix_restful_info(scope = "/v1", path = "item")]
+struct Item {
+    id: Id,
+}
+
+#[async_trait]
+impl Model<Id, NoQuery, NoQuery, Vec<Item>, NoQuery, Item, ()> for Item {
+    async fn find(id: Id, _query: &NoQuery, _state: &()) -> Result<Box<Item>> {
+        Ok(Box::new(Item { id }))
+    }
+    async fn list(_query: &NoQuery, _state: &()) -> Result<Vec<Item>> {
+        Ok(vec![])
+    }
+    async fn delete(self, _query: &NoQuery, _state: &()) -> Result<Item> {
+        Ok(self)
+    }
+}
+
+#[derive(Deserialize, HttpCreate)]
+#[http_create(compat)]
+struct NewItem {}
+
+#[async_trait]
+impl NewModel<Item, NoQuery, ()> for NewItem {
+    async fn save(self, _query: &NoQuery, _state: &()) -> Result<Item> {
+        Ok(Item { id: 1 })
+    }
+}
+
+#[derive(Serialize, Deserialize, HttpUpdate, HttpPatch)]
+#[http_update(compat, id = Id, model = Item)]
+#[http_patch(compat, id = Id, model = Item)]
+struct UpdatableItem {
+    id: Id,
+}
+
+#[async_trait]
+impl UpdatableModel<UpdatableItem, NoQuery, ()> for UpdatableItem {
+    async fn update(self, _query: &NoQuery, _state: &()) -> Result<UpdatableItem> {
+        Ok(self)
+    }
+}
+
+#[async_trait]
+impl PatchableModel<UpdatableItem, NoQuery, ()> for UpdatableItem {
+    async fn patch(self, _query: &NoQuery, _state: &()) -> Result<UpdatableItem> {
+        Ok(self)
+    }
+}
+
+#[derive(Serialize, HttpFind, HttpList)]
+#[actix_restful_info(scope = "/v1", path = "note")]
+struct Note {
+    id: Id,
+}
+
+#[async_trait]
+impl Findable for Note {
+    type Id = Id;
+    type FindQuery = NoQuery;
+    type State = ();
+
+    async fn find(id: Id, _query: &NoQuery, _state: &(), _ctx: &RequestContext) -> Result<Box<Note>> {
+        Ok(Box::new(Note { id }))
+    }
+}
+
+#[async_trait]
+impl Listable for Note {
+    type ListQuery = NoQuery;
+    type ListResult = Vec<Note>;
+    type State = ();
+
+    async fn list(_query: &NoQuery, _state: &(), _ctx: &RequestContext) -> Result<Vec<Note>> {
+        Ok(vec![])
+    }
+}
+
+fn main() {
+    let _ = actix_restful::Resource::<Item>::new()
+        .find()
+        .list()
+        .delete()
+        .create::<NewItem>()
+        .update::<UpdatableItem>()
+        .patch::<UpdatableItem>()
+        .configure();
+    let _ = actix_restful::Resource::<Note>::new().find().list().configure();
+    assert_eq!(Item::scope(), Note::scope());
+}
@@ -0,0 +1,43 @@
+// a model which is not the parent of another one needs neither a `Default` find query nor a `Clone` id
+use actix_restful::{Findable, RequestContext, RestfulPathInfo};
+use actix_restful_derive::{actix_restful_info, HttpFind};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Serialize, Deserialize)]
+struct Id(i64);
+
+impl fmt::Display for Id {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Deserialize)]
+struct FindQuery {
+    #[allow(dead_code)]
+    version: i64,
+}
+
+#[derive(Serialize, HttpFind)]
+#[actix_restful_info(scope = "/v1", path = "item")]
+struct Item {
+    id: Id,
+}
+
+#[async_trait]
+impl Findable for Item {
+    type Id = Id;
+    type FindQuery = FindQuery;
+    type State = ();
+
+    async fn find(id: Id, _query: &FindQuery, _state: &(), _ctx: &RequestContext) -> Result<Box<Item>> {
+        Ok(Box::new(Item { id }))
+    }
+}
+
+fn main() {
+    let _ = actix_web::web::scope(Item::scope()).configure(actix_restful::gen_endpoint!(Item => find));
+}
@@ -0,0 +1,28 @@
+use actix_restful::{NewModel, RequestContext};
+use actix_restful_derive::{actix_restful_info, HttpCreate};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+#[actix_restful_info(scope = "/v1", path = "user")]
+#[derive(Serialize)]
+struct User {
+    login: String,
+}
+
+#[derive(Deserialize, HttpCreate)]
+struct NewUser {
+    login: String,
+}
+
+#[async_trait]
+impl NewModel for NewUser {
+    type Output = User;
+    type Query = actix_restful::NoQuery;
+    type State = ();
+
+    async fn save(self, _query: &Self::Query, _state: &(), _ctx: &RequestContext) -> anyhow::Result<User> {
+        Ok(User { login: self.login })
+    }
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use actix_restful_derive::HttpCreate;
+
+struct SaveQuery;
+struct AppState;
+
+#[derive(HttpCreate)]
+#[http_create(query = SaveQuery, state = AppState, query = SaveQuery)]
+struct NewItem {}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use actix_restful_derive::HttpCreate;
+
+struct SaveQuery;
+struct AppState;
+
+#[derive(HttpCreate)]
+#[http_create(SaveQuery, AppState)]
+struct NewItem<T> {
+    value: T,
+}
+
+fn main() {}
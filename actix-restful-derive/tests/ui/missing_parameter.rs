@@ -0,0 +1,10 @@
+use actix_restful_derive::HttpPatch;
+
+struct Id;
+struct AppState;
+
+#[derive(HttpPatch)]
+#[http_patch(compat, id = Id, state = AppState)]
+struct PatchableItem {}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use actix_restful_derive::HttpPatch;
+
+#[derive(HttpPatch)]
+#[http_patch = "Item"]
+struct PatchableItem {}
+
+fn main() {}
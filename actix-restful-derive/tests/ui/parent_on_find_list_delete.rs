@@ -0,0 +1,12 @@
+use actix_restful_derive::HttpFindListDelete;
+
+struct Id;
+struct Query;
+struct AppState;
+struct Project;
+
+#[derive(HttpFindListDelete)]
+#[http_find_list_delete(Id, Query, Query, Query, AppState, parent = Project)]
+struct Task {}
+
+fn main() {}
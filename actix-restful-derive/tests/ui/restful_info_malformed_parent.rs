@@ -0,0 +1,7 @@
+use actix_restful_derive::{actix_restful_info, HttpFind};
+
+#[derive(HttpFind)]
+#[actix_restful_info(scope = "/v1", path = "task", parent = "Project<")]
+struct Task {}
+
+fn main() {}
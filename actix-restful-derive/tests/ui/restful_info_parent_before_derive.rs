@@ -0,0 +1,27 @@
+use actix_restful::{Findable, NoQuery, RequestContext};
+use actix_restful_derive::{actix_restful_info, HttpFind};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Serialize;
+
+#[actix_restful_info(scope = "/v1", path = "project")]
+struct Project {}
+
+#[actix_restful_info(scope = "/v1", path = "task", parent = "Project")]
+#[derive(Serialize, HttpFind)]
+struct Task {
+    id: i64,
+}
+
+#[async_trait]
+impl Findable for Task {
+    type Id = i64;
+    type FindQuery = NoQuery;
+    type State = ();
+
+    async fn find(id: i64, _query: &NoQuery, _state: &(), _ctx: &RequestContext) -> Result<Box<Task>> {
+        Ok(Box::new(Task { id }))
+    }
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use actix_restful_derive::actix_restful_info;
+use serde::Serialize;
+
+#[actix_restful_info(scope = "/v1", path = "item", filter = "content, status")]
+#[derive(Serialize)]
+struct Item {
+    id: i64,
+    content: String,
+    #[serde(skip)]
+    status: String,
+}
+
+fn main() {}
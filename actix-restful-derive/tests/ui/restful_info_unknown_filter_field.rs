@@ -0,0 +1,9 @@
+use actix_restful_derive::actix_restful_info;
+
+#[actix_restful_info(scope = "/v1", path = "item", filter = "content, status")]
+struct Item {
+    id: i64,
+    content: String,
+}
+
+fn main() {}
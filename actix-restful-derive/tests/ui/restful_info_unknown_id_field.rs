@@ -0,0 +1,8 @@
+use actix_restful_derive::actix_restful_info;
+
+#[actix_restful_info(scope = "/v1", path = "user", id_field = "name")]
+struct User {
+    login: String,
+}
+
+fn main() {}
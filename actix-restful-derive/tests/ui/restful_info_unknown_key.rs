@@ -0,0 +1,6 @@
+use actix_restful_derive::actix_restful_info;
+
+#[actix_restful_info(scope = "/v1", path = "item", plural)]
+struct Item {}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use actix_restful_derive::Restful;
+
+#[derive(Restful)]
+#[restful(scope = "/v1", id = u32)]
+struct Item {}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use actix_restful_derive::Restful;
+
+struct Id;
+
+#[derive(Restful)]
+#[restful(scope = "/v1", path = "item", id = Id, read_only)]
+struct Item {}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use actix_restful_derive::HttpUpdate;
+
+struct Id;
+struct UpdateQuery;
+struct Item;
+struct FindQuery;
+struct AppState;
+
+#[derive(HttpUpdate)]
+#[http_update(Id, UpdateQuery, Item, FindQuery, AppState, owner = Item)]
+struct UpdatableItem {}
+
+fn main() {}
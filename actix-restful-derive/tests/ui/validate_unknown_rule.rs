@@ -0,0 +1,9 @@
+use actix_restful_derive::Validate;
+
+#[derive(Validate)]
+struct NewItem {
+    #[restful(length(max = 200), size = 3)]
+    content: String,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use actix_restful_derive::HttpFindListDelete;
+
+struct Id;
+struct FindQuery;
+struct AppState;
+
+#[derive(HttpFindListDelete)]
+#[http_find_list_delete(Id, FindQuery, AppState)]
+struct Item {}
+
+fn main() {}
@@ -0,0 +1,187 @@
+//! The generic model traits of the previous versions, enabled with the `compat` feature.
+//!
+//! Import them in place of the traits of the crate root :
+//!
+//! ```ignore
+//! use actix_restful::compat::{Model, NewModel, UpdatableModel};
+//! ```
+//!
+//! The `compat` flag of a derive attribute selects these traits for its model, the other models of the crate
+//! keeping the traits of the crate root : `#[http_find_list_delete(compat, Id, FindQuery, ListQuery, DeleteQuery, AppState)]`.
+//! The derives can't read the types of the handlers from these traits, the id is then expected, the omitted
+//! query types default to `NoQuery` and the state to `()`. The methods of these traits don't receive the `RequestContext`.
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// A trait to implement on your main struct entity via the HttpFindListDelete derive macro :
+///
+/// The methods may fail with a `RestError` to choose the status code of the response,
+/// e.g. `RestError::NotFound` in `find` answers 404. Any other error answers 500.
+///
+/// ```ignore
+///
+/// use actix_restful::compat::Model;
+/// use actix_restful::{
+///  RestfulPathInfo
+/// };
+/// use actix_restful_derive::{HttpFindListDelete, actix_restful_info};
+/// use anyhow::Result;
+/// use async_trait::async_trait;
+///
+/// #[derive(HttpFindListDelete)]
+/// #[http_find_list_delete(compat, Id, FindQuery, ListQuery, DeleteQuery, AppState)]
+/// #[actix_restful_info(scope = "/v1", path = "item")]
+/// struct Item {
+/// }
+///
+/// #[async_trait]
+/// impl Model<Id, FindQuery, ListQuery, ListResult, DeleteQuery, DeleteResult, AppState> for Item {
+///    async fn find(id: Id, _query: &FindQuery, _state: &AppState) -> Result<Box<Item>> {
+///    }
+///    async fn list(_query: &ListQuery, _state: &AppState) -> Result<ListResult> {
+///    }
+///    async fn delete(mut self: Self, _query: &DeleteQuery, _state: &AppState) -> Result<DeleteResult> {
+///    }
+/// }
+/// ```
+#[async_trait]
+pub trait Model<ID, FQ, LQ, LR, DQ, DR, AppState> {
+    async fn find(id: ID, query: &FQ, state: &AppState) -> Result<Box<Self>>;
+    async fn list(query: &LQ, state: &AppState) -> Result<LR>;
+    async fn delete(self, query: &DQ, state: &AppState) -> Result<DR>;
+}
+
+/// The counterpart of `Model` for a model nested under a parent model :
+///
+/// ```ignore
+/// #[derive(HttpFindListDelete)]
+/// #[http_find_list_delete(compat, Id, FindQuery, ListQuery, DeleteQuery, AppState)]
+/// #[actix_restful_info(scope = "/v1", path = "task", parent = "Project")]
+/// struct Task {
+/// }
+///
+/// #[async_trait]
+/// impl NestedModel<ProjectId, Id, FindQuery, ListQuery, ListResult, DeleteQuery, DeleteResult, AppState> for Task {
+///    async fn find(project_id: ProjectId, id: Id, _query: &FindQuery, _state: &AppState) -> Result<Box<Task>> {
+///    }
+///    async fn list(project_id: ProjectId, _query: &ListQuery, _state: &AppState) -> Result<ListResult> {
+///    }
+///    async fn delete(mut self, project_id: ProjectId, _query: &DeleteQuery, _state: &AppState) -> Result<DeleteResult> {
+///    }
+/// }
+/// ```
+///
+/// The routes are `/v1/project/{project_id}/task` and `/v1/project/{project_id}/task/{id}`.
+/// The handlers call `find` on the parent first, and answer its error when it fails,
+/// so return a `RestError::NotFound` from the parent `find` to answer 404.
+#[async_trait]
+pub trait NestedModel<PID, ID, FQ, LQ, LR, DQ, DR, AppState> {
+    async fn find(parent_id: PID, id: ID, query: &FQ, state: &AppState) -> Result<Box<Self>>;
+    async fn list(parent_id: PID, query: &LQ, state: &AppState) -> Result<LR>;
+    async fn delete(self, parent_id: PID, query: &DQ, state: &AppState) -> Result<DR>;
+}
+
+/// A trait to implement on your creatable entity entity
+///
+/// `save` may fail with a `RestError`, e.g. `RestError::Conflict` on a uniqueness violation answers 409.
+///
+/// ```ignore
+///
+/// use actix_restful::compat::NewModel;
+/// use actix_restful_derive::HttpCreate;
+/// use anyhow::Result;
+/// use async_trait::async_trait;
+///
+/// #[derive(HttpCreate)]
+/// #[http_create(compat, SaveQuery, AppState)]
+/// struct NewItem {
+/// }
+///
+/// #[async_trait]
+/// impl NewModel<Item, SaveQuery, AppState> for NewItem {
+///    async fn save(self, _query: &SaveQuery, _state: &AppState) -> Result<Item> {
+///    }
+/// }
+/// ```
+#[async_trait]
+pub trait NewModel<T, Q, AppState> {
+    async fn save(self, query: &Q, state: &AppState) -> Result<T>;
+}
+
+/// The counterpart of `NewModel` for a model nested under a parent model,
+/// derived with `#[http_create(compat, SaveQuery, AppState, parent = Project)]`
+#[async_trait]
+pub trait NestedNewModel<PID, T, Q, AppState> {
+    async fn save(self, parent_id: PID, query: &Q, state: &AppState) -> Result<T>;
+}
+
+/// A trait to implement on your Updatable entity
+///
+/// `update` may fail with a `RestError` to choose the status code of the response.
+///
+/// ```ignore
+///
+/// use actix_restful::compat::UpdatableModel;
+/// use actix_restful_derive::HttpUpdate;
+/// use anyhow::Result;
+/// use async_trait::async_trait;
+///
+/// #[derive(HttpUpdate)]
+/// #[http_update(compat, Id, UpdateQuery, Item, FindQuery, AppState)]
+/// struct UpdatableItem {
+/// }
+///
+/// #[async_trait]
+/// impl UpdatableModel<UpdatableItem, UpdateQuery, AppState> for UpdatableItem {
+///     async fn update(mut self: Self, _query: &UpdateQuery, _state: &AppState) -> Result<UpdatableItem> {
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait UpdatableModel<T, Q, AppState> {
+    async fn update(self, query: &Q, state: &AppState) -> Result<T>;
+}
+
+/// The counterpart of `UpdatableModel` for a model nested under a parent model,
+/// derived with `#[http_update(compat, Id, UpdateQuery, Task, FindQuery, AppState, parent = Project)]`
+#[async_trait]
+pub trait NestedUpdatableModel<PID, T, Q, AppState> {
+    async fn update(self, parent_id: PID, query: &Q, state: &AppState) -> Result<T>;
+}
+
+/// A trait to implement on your Patchable entity
+///
+/// The PATCH body is a JSON Merge Patch (RFC 7396) document, or a JSON Patch (RFC 6902) document
+/// when sent as `application/json-patch+json`. It is applied on top of the entity loaded with
+/// `Model::find`, and `patch` is called on the result. A failing JSON Patch `test` operation answers 409.
+///
+/// ```ignore
+///
+/// use actix_restful::compat::PatchableModel;
+/// use actix_restful_derive::HttpPatch;
+/// use anyhow::Result;
+/// use async_trait::async_trait;
+///
+/// #[derive(HttpPatch)]
+/// #[http_patch(compat, Id, PatchQuery, Item, FindQuery, AppState)]
+/// struct PatchableItem {
+/// }
+///
+/// #[async_trait]
+/// impl PatchableModel<PatchableItem, PatchQuery, AppState> for PatchableItem {
+///     async fn patch(mut self, _query: &PatchQuery, _state: &AppState) -> Result<PatchableItem> {
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait PatchableModel<T, Q, AppState> {
+    async fn patch(self, query: &Q, state: &AppState) -> Result<T>;
+}
+
+/// The counterpart of `PatchableModel` for a model nested under a parent model,
+/// derived with `#[http_patch(compat, Id, PatchQuery, Task, FindQuery, AppState, parent = Project)]`
+#[async_trait]
+pub trait NestedPatchableModel<PID, T, Q, AppState> {
+    async fn patch(self, parent_id: PID, query: &Q, state: &AppState) -> Result<T>;
+}
@@ -0,0 +1,89 @@
+use crate::context::{ContextExtensions, CopyExtensionFn};
+use crate::cursor::CursorKey;
+use crate::RestError;
+use actix_web::dev::Extensions;
+use actix_web::HttpRequest;
+use std::sync::Arc;
+
+type ProblemTypeFn = dyn Fn(&RestError) -> String + Send + Sync;
+
+/// Settings of the generated handlers, registered as app data on the actix App :
+///
+/// ```ignore
+/// actix_web::App::new()
+///     .app_data(RestfulConfig::default().problem_type(|err| {
+///         format!("https://api.example.com/problems/{}", err.kind())
+///     }))
+/// ```
+///
+/// The handlers fall back to `RestfulConfig::default()` when none is registered.
+#[derive(Clone, Default)]
+pub struct RestfulConfig {
+    problem_type: Option<Arc<ProblemTypeFn>>,
+    pretty: bool,
+    context_extensions: Vec<Arc<CopyExtensionFn>>,
+    cursor_key: Option<CursorKey>,
+}
+
+impl RestfulConfig {
+    /// Sets the hook building the `type` URI of the problem documents, `about:blank` by default
+    pub fn problem_type<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&RestError) -> String + Send + Sync + 'static,
+    {
+        self.problem_type = Some(Arc::new(f));
+        self
+    }
+
+    /// Pretty prints the json bodies, handy during development
+    pub fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Copies the request extension of type `T` in the `RequestContext` handed to the models,
+    /// e.g. the user inserted by an authentication middleware
+    pub fn context_extension<T: Clone + Send + Sync + 'static>(mut self) -> Self {
+        self.context_extensions
+            .push(Arc::new(|from: &Extensions, to: &mut ContextExtensions| {
+                if let Some(value) = from.get::<T>() {
+                    to.insert(value.clone());
+                }
+            }));
+        self
+    }
+
+    /// Sets the secret signing the tokens of the `CursorQuery` pagination, which must be shared by the instances
+    /// of the app and kept across restarts for the issued cursors to stay valid. It is set before the routes of
+    /// the lists answering a `CursorPage` are configured, which panics otherwise
+    pub fn cursor_key(mut self, secret: impl AsRef<[u8]>) -> Self {
+        self.cursor_key = Some(CursorKey::new(secret.as_ref()));
+        self
+    }
+
+    pub fn is_pretty(&self) -> bool {
+        self.pretty
+    }
+
+    /// The config registered on the app, or the default one
+    pub fn from_req(req: &HttpRequest) -> Self {
+        req.app_data::<RestfulConfig>().cloned().unwrap_or_default()
+    }
+
+    pub(crate) fn problem_type_of(&self, err: &RestError) -> String {
+        match &self.problem_type {
+            Some(f) => f(err),
+            None => String::from("about:blank"),
+        }
+    }
+
+    pub(crate) fn signing_key(&self) -> Option<&CursorKey> {
+        self.cursor_key.as_ref()
+    }
+
+    pub(crate) fn copy_extensions(&self, from: &Extensions, to: &mut ContextExtensions) {
+        for copy in &self.context_extensions {
+            copy(from, to);
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use crate::cursor::CursorKey;
+use crate::{Fields, RestfulConfig};
+use actix_web::dev::{Extensions, Payload};
+use actix_web::http::{HeaderMap, Method};
+use actix_web::{FromRequest, HttpRequest};
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::future::{ready, Ready};
+use std::net::SocketAddr;
+
+/// The request answered by a generated handler, handed to the methods of the model traits.
+///
+/// The `HttpRequest` of actix is neither `Send` nor `Sync`, so the context holds a copy of its
+/// method, path, query string, headers, peer address and matched route, e.g. to read a locale or a request id :
+///
+/// ```ignore
+/// async fn find(id: i64, _query: &NoQuery, _state: &AppState, ctx: &RequestContext) -> Result<Box<Item>> {
+///     let locale = ctx.header("accept-language").unwrap_or("en");
+/// }
+/// ```
+///
+/// The request extensions are copied for the types registered on the `RestfulConfig`,
+/// e.g. the user inserted by an authentication middleware :
+///
+/// ```ignore
+/// actix_web::App::new()
+///     .app_data(RestfulConfig::default().context_extension::<User>())
+///
+/// let user = ctx.extensions().get::<User>();
+/// ```
+///
+/// Its `Debug` output redacts the values of the headers holding credentials, e.g. `Authorization` and `Cookie`.
+pub struct RequestContext {
+    method: Method,
+    path: String,
+    query_string: String,
+    headers: HeaderMap,
+    peer_addr: Option<SocketAddr>,
+    matched_route: Option<String>,
+    extensions: ContextExtensions,
+    cursor_key: Option<CursorKey>,
+    fields: Fields,
+}
+
+impl RequestContext {
+    pub fn from_req(req: &HttpRequest) -> Self {
+        let mut extensions = ContextExtensions::default();
+        let mut cursor_key = None;
+        if let Some(config) = req.app_data::<RestfulConfig>() {
+            config.copy_extensions(&req.extensions(), &mut extensions);
+            cursor_key = config.signing_key().cloned();
+        }
+        RequestContext {
+            method: req.method().clone(),
+            path: req.path().to_owned(),
+            query_string: req.query_string().to_owned(),
+            headers: req.headers().clone(),
+            peer_addr: req.peer_addr(),
+            matched_route: req.match_pattern(),
+            extensions,
+            cursor_key,
+            fields: req.extensions().get::<Fields>().cloned().unwrap_or_default(),
+        }
+    }
+
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The query string of the request, without the leading `?`
+    pub fn query_string(&self) -> &str {
+        &self.query_string
+    }
+
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// The value of a header, `None` when it is missing or not visible ASCII
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+
+    /// The address of the peer of the connection, not the client behind a proxy
+    pub fn peer_addr(&self) -> Option<SocketAddr> {
+        self.peer_addr
+    }
+
+    /// The pattern of the route answering the request, e.g. `/v1/item/{id}`
+    pub fn matched_route(&self) -> Option<&str> {
+        self.matched_route.as_deref()
+    }
+
+    pub fn extensions(&self) -> &ContextExtensions {
+        &self.extensions
+    }
+
+    /// The fields requested for the response, checked by the generated `http_find` and `http_list` handlers
+    pub fn fields(&self) -> &Fields {
+        &self.fields
+    }
+
+    pub(crate) fn cursor_key(&self) -> Option<&CursorKey> {
+        self.cursor_key.as_ref()
+    }
+}
+
+/// The headers whose values are redacted by the `Debug` output of a `RequestContext`
+const SENSITIVE_HEADERS: [&str; 5] = ["authorization", "proxy-authorization", "cookie", "set-cookie", "x-api-key"];
+
+impl fmt::Debug for RequestContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestContext")
+            .field("method", &self.method)
+            .field("path", &self.path)
+            .field("query_string", &self.query_string)
+            .field("headers", &RedactedHeaders(&self.headers))
+            .field("peer_addr", &self.peer_addr)
+            .field("matched_route", &self.matched_route)
+            .field("extensions", &self.extensions)
+            .field("cursor_key", &self.cursor_key)
+            .field("fields", &self.fields)
+            .finish()
+    }
+}
+
+/// The headers of a request, the values of the `SENSITIVE_HEADERS` being redacted
+struct RedactedHeaders<'a>(&'a HeaderMap);
+
+impl fmt::Debug for RedactedHeaders<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut map = f.debug_map();
+        for (name, value) in self.0.iter() {
+            if SENSITIVE_HEADERS.contains(&name.as_str()) {
+                map.entry(name, &"<redacted>");
+            } else {
+                map.entry(name, value);
+            }
+        }
+        map.finish()
+    }
+}
+
+impl FromRequest for RequestContext {
+    type Config = ();
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, actix_web::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(Ok(RequestContext::from_req(req)))
+    }
+}
+
+/// The request extensions copied in a `RequestContext`, by type
+#[derive(Default)]
+pub struct ContextExtensions {
+    map: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+impl ContextExtensions {
+    pub fn insert<T: Send + Sync + 'static>(&mut self, value: T) {
+        self.map.insert(TypeId::of::<T>(), Box::new(value));
+    }
+
+    pub fn get<T: 'static>(&self) -> Option<&T> {
+        self.map
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref())
+    }
+
+    pub fn contains<T: 'static>(&self) -> bool {
+        self.map.contains_key(&TypeId::of::<T>())
+    }
+}
+
+impl fmt::Debug for ContextExtensions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContextExtensions")
+            .field("len", &self.map.len())
+            .finish()
+    }
+}
+
+pub(crate) type CopyExtensionFn = dyn Fn(&Extensions, &mut ContextExtensions) + Send + Sync;
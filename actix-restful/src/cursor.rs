@@ -0,0 +1,222 @@
+use crate::{json_response, pagination::page_link, query::deserialize_params, RequestContext, RestError};
+use actix_web::http::{header, HeaderValue, StatusCode};
+use actix_web::{web, HttpRequest, HttpResponse};
+use hmac::{Hmac, Mac};
+use serde::de::{Deserialize, DeserializeOwned, Deserializer};
+use serde::Serialize;
+use sha2::Sha256;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Whether a `RestfulConfig::cursor_key` was set, see `check_cursor_key`
+static CURSOR_KEY_SET: AtomicBool = AtomicBool::new(false);
+
+/// Panics when the list of `path`, answering a `CursorPage`, is configured before any `RestfulConfig::cursor_key` is set
+pub(crate) fn check_cursor_key(path: &str) {
+    if !CURSOR_KEY_SET.load(Ordering::Relaxed) {
+        panic!(
+            "the list of `{}` answers cursors, which require a secret : register `RestfulConfig::default().cursor_key(..)` \
+             on the app before configuring its routes",
+            path
+        );
+    }
+}
+
+/// The secret signing the cursor tokens, see `RestfulConfig::cursor_key`
+#[derive(Clone)]
+pub(crate) struct CursorKey(Arc<[u8]>);
+
+impl CursorKey {
+    pub(crate) fn new(secret: &[u8]) -> Self {
+        CURSOR_KEY_SET.store(true, Ordering::Relaxed);
+        CursorKey(Arc::from(secret))
+    }
+
+    /// The signature of the payload of a token, for the request `ctx`, see `binding`
+    fn mac(&self, ctx: &RequestContext, payload: &[u8]) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.0).expect("HMAC takes keys of any size");
+        mac.update(&binding(ctx));
+        mac.update(&[0]);
+        mac.update(payload);
+        mac
+    }
+
+    /// The token `payload.signature` of the sort key `key`, both encoded in base64url
+    fn sign<K: Serialize>(&self, ctx: &RequestContext, key: &K) -> Result<String, RestError> {
+        let payload = serde_json::to_vec(key).map_err(|err| RestError::Internal(err.into()))?;
+        let signature = self.mac(ctx, &payload).finalize().into_bytes();
+        Ok(format!(
+            "{}.{}",
+            base64::encode_config(&payload, base64::URL_SAFE_NO_PAD),
+            base64::encode_config(signature, base64::URL_SAFE_NO_PAD)
+        ))
+    }
+
+    /// The sort key of a token, `None` when it was not signed with this secret for the path and the query of `ctx`
+    fn verify<K: DeserializeOwned>(&self, ctx: &RequestContext, token: &str) -> Option<K> {
+        let (payload, signature) = token.split_once('.')?;
+        let payload = base64::decode_config(payload, base64::URL_SAFE_NO_PAD).ok()?;
+        let signature = base64::decode_config(signature, base64::URL_SAFE_NO_PAD).ok()?;
+        self.mac(ctx, &payload).verify_slice(&signature).ok()?;
+        serde_json::from_slice(&payload).ok()
+    }
+}
+
+impl fmt::Debug for CursorKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CursorKey(..)")
+    }
+}
+
+/// What the tokens of a request are bound to : its path, e.g. `/v1/project/1/task` rather than the route of the tasks of
+/// any project, and the parameters of its query string selecting or ordering the items, e.g. the `sort` and the
+/// `filter[..]`, decoded and sorted. The `cursor`, `limit` and `fields` parameters are left out, the pages following
+/// a cursor may be fetched with another limit or other fields.
+fn binding(ctx: &RequestContext) -> Vec<u8> {
+    let mut params = web::Query::<Vec<(String, String)>>::from_query(ctx.query_string())
+        .map(web::Query::into_inner)
+        .unwrap_or_default();
+    params.retain(|(key, _)| !["cursor", "limit", "fields"].contains(&key.as_str()) && !key.starts_with("fields["));
+    params.sort();
+    serde_json::to_vec(&(ctx.path(), params)).unwrap_or_default()
+}
+
+/// The key of the context of a request, failing when no `RestfulConfig::cursor_key` is registered
+fn cursor_key(ctx: &RequestContext) -> Result<&CursorKey, RestError> {
+    ctx.cursor_key().ok_or_else(|| {
+        RestError::Internal(anyhow::anyhow!("the cursor tokens require a secret, see `RestfulConfig::cursor_key`"))
+    })
+}
+
+/// The `cursor` and `limit` parameters of a query string for a keyset pagination, `DEFAULT_LIMIT` when the `limit`
+/// is missing, and capped to `MAX_LIMIT`.
+///
+/// The cursor is an opaque token holding the sort key of the last item of the previous page, signed with the secret of
+/// `RestfulConfig::cursor_key` : a token which was altered, or issued for another path, e.g. the list of another parent,
+/// or with other parameters, e.g. another `sort` or other filters, is answered `400 Bad Request`.
+/// `list` reads the sort key with `after`, fetches the items following it, one more than the limit, and answers them
+/// as a `CursorPage` with the sort key of their last item :
+///
+/// ```ignore
+/// async fn list(query: &ListQuery, state: &AppState, ctx: &RequestContext) -> Result<CursorPage<Item>> {
+///     let after: Option<(DateTime<Utc>, i64)> = query.page.after(ctx)?;
+///     // SELECT .. WHERE (created_at, id) > $after ORDER BY created_at, id LIMIT query.page.limit() + 1
+///     Ok(CursorPage::new(items, &query.page, ctx, |item| (item.created_at, item.id))?)
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CursorQuery<const DEFAULT_LIMIT: usize = 20, const MAX_LIMIT: usize = 100> {
+    cursor: Option<String>,
+    limit: usize,
+}
+
+impl<const DEFAULT_LIMIT: usize, const MAX_LIMIT: usize> CursorQuery<DEFAULT_LIMIT, MAX_LIMIT> {
+    pub fn new(cursor: Option<String>, limit: usize) -> Self {
+        CursorQuery { cursor, limit: limit.clamp(1, MAX_LIMIT.max(1)) }
+    }
+
+    /// The token of the cursor, `None` for the first page
+    pub fn cursor(&self) -> Option<&str> {
+        self.cursor.as_deref()
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// The sort key of the last item of the previous page, `None` for the first page
+    pub fn after<K: DeserializeOwned>(&self, ctx: &RequestContext) -> Result<Option<K>, RestError> {
+        let token = match &self.cursor {
+            Some(token) => token,
+            None => return Ok(None),
+        };
+        let key = cursor_key(ctx)?;
+        match key.verify(ctx, token) {
+            Some(after) => Ok(Some(after)),
+            None => Err(RestError::BadRequest(String::from("invalid cursor"))),
+        }
+    }
+}
+
+impl<const DEFAULT_LIMIT: usize, const MAX_LIMIT: usize> Default for CursorQuery<DEFAULT_LIMIT, MAX_LIMIT> {
+    fn default() -> Self {
+        CursorQuery::new(None, DEFAULT_LIMIT)
+    }
+}
+
+impl<'de, const DEFAULT_LIMIT: usize, const MAX_LIMIT: usize> Deserialize<'de>
+    for CursorQuery<DEFAULT_LIMIT, MAX_LIMIT>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (cursor, limit) = deserialize_params(deserializer, (None, DEFAULT_LIMIT), |(cursor, limit), key, value| {
+            match key.as_str() {
+                "cursor" if !value.is_empty() => *cursor = Some(value),
+                "limit" => {
+                    *limit =
+                        value.parse().map_err(|_| format!("invalid `limit` `{}`, expected a positive integer", value))?
+                }
+                _ => {}
+            }
+            Ok(())
+        })?;
+        Ok(CursorQuery::new(cursor, limit))
+    }
+}
+
+/// A page of a list paginated with a `CursorQuery`, answered with the `Link` header of the first and next pages.
+///
+/// The body is the envelope `{ "items": [..], "limit": 20, "next_cursor": ".." }`, without a `next_cursor` on the last page.
+#[derive(Clone, Debug, Serialize)]
+pub struct CursorPage<T> {
+    pub items: Vec<T>,
+    pub limit: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+impl<T> CursorPage<T> {
+    /// The page of the `items` following the cursor of `query`, fetched with one more item than its limit
+    /// to know whether a next page follows, whose cursor holds the `sort_key` of the last item of the page
+    pub fn new<K, F, const DEFAULT_LIMIT: usize, const MAX_LIMIT: usize>(
+        mut items: Vec<T>,
+        query: &CursorQuery<DEFAULT_LIMIT, MAX_LIMIT>,
+        ctx: &RequestContext,
+        sort_key: F,
+    ) -> Result<Self, RestError>
+    where
+        K: Serialize,
+        F: Fn(&T) -> K,
+    {
+        let limit = query.limit();
+        let mut next_cursor = None;
+        if items.len() > limit {
+            items.truncate(limit);
+            if let Some(last) = items.last() {
+                let key = cursor_key(ctx)?;
+                next_cursor = Some(key.sign(ctx, &sort_key(last))?);
+            }
+        }
+        Ok(CursorPage { items, limit, next_cursor })
+    }
+}
+
+/// Answers 200 OK with the page and the `Link` header of the first and next pages.
+///
+/// The links keep the other parameters of the query string, e.g. the filters, with the `cursor` and `limit` of their page.
+pub fn cursor_response<T: Serialize>(req: &HttpRequest, page: &CursorPage<T>) -> HttpResponse {
+    let mut res = json_response(req, StatusCode::OK, page);
+    if res.status() != StatusCode::OK {
+        return res;
+    }
+    let mut links = vec![page_link(req, &format!("limit={}", page.limit), "first")];
+    if let Some(cursor) = &page.next_cursor {
+        links.push(page_link(req, &format!("cursor={}&limit={}", cursor, page.limit), "next"));
+    }
+    if let Ok(value) = HeaderValue::from_str(&links.join(", ")) {
+        res.headers_mut().insert(header::LINK, value);
+    }
+    res
+}
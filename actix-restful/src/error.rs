@@ -0,0 +1,144 @@
+use crate::{FieldError, Problem, RestfulConfig, ValidationErrors};
+use actix_web::error::InternalError;
+use actix_web::{http::StatusCode, HttpRequest, HttpResponse, ResponseError};
+use std::fmt;
+
+/// The error type the generated handlers understand.
+///
+/// The model traits return `anyhow::Result`, so a `RestError` can be returned from
+/// `find`, `list`, `delete`, `save` or `update` with `Err(RestError::Conflict(..).into())`
+/// or the `?` operator. The generated handlers turn it back into a `RestError` and
+/// answer with the matching status code. Any other error is reported as a 500.
+///
+/// ```ignore
+/// async fn find(id: Id, _query: &FindQuery, state: &AppState) -> Result<Box<Item>> {
+///     state.items.get(&id)
+///         .map(|item| Box::new(item.clone()))
+///         .ok_or_else(|| RestError::NotFound(format!("item {} does not exist", id)).into())
+/// }
+/// ```
+#[derive(Debug)]
+pub enum RestError {
+    /// 400 Bad Request
+    BadRequest(String),
+    /// 400 Bad Request, locating the value of a JSON body which does not deserialize, see `parse_json`
+    InvalidBody(Box<FieldError>),
+    /// 401 Unauthorized
+    Unauthorized(String),
+    /// 403 Forbidden
+    Forbidden(String),
+    /// 404 Not Found
+    NotFound(String),
+    /// 405 Method Not Allowed, answered by the routes of a model for the methods it doesn't support
+    MethodNotAllowed(String),
+    /// 409 Conflict, e.g. a uniqueness violation
+    Conflict(String),
+    /// 413 Payload Too Large
+    PayloadTooLarge(String),
+    /// 415 Unsupported Media Type
+    UnsupportedMediaType(String),
+    /// 422 Unprocessable Entity
+    Validation(String),
+    /// 422 Unprocessable Entity, listing the invalid fields of a payload, see `Validate`
+    Invalid(ValidationErrors),
+    /// 500 Internal Server Error
+    Internal(anyhow::Error),
+}
+
+impl RestError {
+    /// The status code the generated handlers answer with for this error
+    pub fn status(&self) -> StatusCode {
+        match self {
+            RestError::BadRequest(_) | RestError::InvalidBody(_) => StatusCode::BAD_REQUEST,
+            RestError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            RestError::Forbidden(_) => StatusCode::FORBIDDEN,
+            RestError::NotFound(_) => StatusCode::NOT_FOUND,
+            RestError::MethodNotAllowed(_) => StatusCode::METHOD_NOT_ALLOWED,
+            RestError::Conflict(_) => StatusCode::CONFLICT,
+            RestError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            RestError::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            RestError::Validation(_) | RestError::Invalid(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            RestError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// A short identifier of the error, handy to build problem type URIs
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RestError::BadRequest(_) => "bad-request",
+            RestError::InvalidBody(_) => "invalid-body",
+            RestError::Unauthorized(_) => "unauthorized",
+            RestError::Forbidden(_) => "forbidden",
+            RestError::NotFound(_) => "not-found",
+            RestError::MethodNotAllowed(_) => "method-not-allowed",
+            RestError::Conflict(_) => "conflict",
+            RestError::PayloadTooLarge(_) => "payload-too-large",
+            RestError::UnsupportedMediaType(_) => "unsupported-media-type",
+            RestError::Validation(_) | RestError::Invalid(_) => "validation",
+            RestError::Internal(_) => "internal",
+        }
+    }
+
+    /// The `application/problem+json` response answered to `req`, logging an `Internal` error whose detail is not answered
+    pub fn to_response(&self, req: &HttpRequest) -> HttpResponse {
+        if let RestError::Internal(err) = self {
+            log::error!("{} {} : {:?}", req.method(), req.path(), err);
+        }
+        Problem::from_req(self, req).into_response()
+    }
+
+    /// The error to fail an extractor with, answered with the problem document of `req`
+    pub fn into_error(self, req: &HttpRequest) -> actix_web::Error {
+        let response = self.to_response(req);
+        InternalError::from_response(self, response).into()
+    }
+}
+
+impl fmt::Display for RestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RestError::BadRequest(msg)
+            | RestError::Unauthorized(msg)
+            | RestError::Forbidden(msg)
+            | RestError::NotFound(msg)
+            | RestError::MethodNotAllowed(msg)
+            | RestError::Conflict(msg)
+            | RestError::PayloadTooLarge(msg)
+            | RestError::UnsupportedMediaType(msg)
+            | RestError::Validation(msg) => f.write_str(msg),
+            RestError::InvalidBody(err) if err.field.is_empty() => write!(f, "invalid body : {}", err.message),
+            RestError::InvalidBody(err) => write!(f, "invalid body at {} : {}", err.field, err.message),
+            RestError::Invalid(errors) => write!(f, "{}", errors),
+            RestError::Internal(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for RestError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RestError::Internal(err) => Some(err.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// Recovers a `RestError` returned by a model method, anything else becomes `Internal`
+impl From<anyhow::Error> for RestError {
+    fn from(err: anyhow::Error) -> Self {
+        match err.downcast::<RestError>() {
+            Ok(err) => err,
+            Err(err) => RestError::Internal(err),
+        }
+    }
+}
+
+impl ResponseError for RestError {
+    fn status_code(&self) -> StatusCode {
+        self.status()
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        Problem::new(self, &RestfulConfig::default()).into_response()
+    }
+}
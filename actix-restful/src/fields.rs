@@ -0,0 +1,165 @@
+use crate::{json_response, RestError};
+use actix_web::http::StatusCode;
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// The sparse fieldset of a request, `?fields=id,content&fields[author]=name` : the fields of the entity kept
+/// in the response, and the fields kept in its nested objects. The fields are named as serde serializes them,
+/// and an empty `fields=` answers the whole entity.
+///
+/// The generated `http_find` and `http_list` handlers check the requested fields against the fields of the model,
+/// answering `400 Bad Request` for an unknown one, project their response on them, and hand them to the models in the
+/// `RequestContext`, e.g. to select only these columns :
+///
+/// ```ignore
+/// async fn find(id: i64, _query: &NoQuery, state: &AppState, ctx: &RequestContext) -> Result<Box<Item>> {
+///     let with_content = ctx.fields().contains("content");
+/// }
+/// ```
+///
+/// ```
+/// use actix_restful::Fields;
+///
+/// let fields = Fields::parse("fields=id,author&fields[author]=name&limit=10").unwrap();
+/// assert!(fields.contains("author") && !fields.contains("content"));
+/// assert_eq!(fields.nested("author"), Some(&[String::from("name")][..]));
+///
+/// let item = serde_json::json!({ "id": 1, "content": "test", "author": { "name": "Ada", "email": "ada@example.com" } });
+/// assert_eq!(fields.project(item), serde_json::json!({ "id": 1, "author": { "name": "Ada" } }));
+///
+/// assert!(Fields::parse("fields=").unwrap().is_empty());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Fields {
+    names: Option<Vec<String>>,
+    nested: BTreeMap<String, Vec<String>>,
+}
+
+impl Fields {
+    /// Reads the `fields` parameters of a query string, the other parameters being ignored
+    pub fn parse(query_string: &str) -> Result<Fields, RestError> {
+        let params = web::Query::<Vec<(String, String)>>::from_query(query_string)
+            .map_err(|err| RestError::BadRequest(err.to_string()))?;
+        let mut fields = Fields::default();
+        for (key, value) in params.into_inner() {
+            let names: Vec<String> =
+                value.split(',').map(str::trim).filter(|name| !name.is_empty()).map(String::from).collect();
+            // an empty `fields=` picks no field, the whole entity is answered
+            if key == "fields" && !names.is_empty() {
+                fields.names.get_or_insert_with(Vec::new).extend(names);
+            } else if let Some(rest) = key.strip_prefix("fields[") {
+                let field = rest.strip_suffix(']').filter(|field| !field.is_empty() && !field.contains('[')).ok_or_else(
+                    || RestError::BadRequest(format!("malformed `{}`, expected `fields` or `fields[field]`", key)),
+                )?;
+                if !names.is_empty() {
+                    fields.nested.entry(field.to_string()).or_default().extend(names);
+                }
+            }
+        }
+        Ok(fields)
+    }
+
+    /// Reads the fieldset of `req` for the model whose fields are `names`, see `RestfulPathInfo::field_names`,
+    /// and keeps it in the request for its `RequestContext`
+    pub fn from_req(req: &HttpRequest, names: &[&str]) -> Result<Fields, RestError> {
+        let fields = Fields::parse(req.query_string())?;
+        fields.check(names)?;
+        req.extensions_mut().insert(fields.clone());
+        Ok(fields)
+    }
+
+    /// Checks the requested fields, and the fields whose nested fields are requested, against `names`,
+    /// any field being accepted when `names` is empty
+    pub fn check(&self, names: &[&str]) -> Result<(), RestError> {
+        if names.is_empty() {
+            return Ok(());
+        }
+        let requested = self.names.iter().flatten().chain(self.nested.keys());
+        match requested.into_iter().find(|field| !names.contains(&field.as_str())) {
+            Some(field) => Err(RestError::BadRequest(format!(
+                "unknown field `{}` in `fields`, expected one of `{}`",
+                field,
+                names.join("`, `")
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Whether no field is picked, the whole entity being answered
+    pub fn is_empty(&self) -> bool {
+        self.names.is_none() && self.nested.is_empty()
+    }
+
+    /// The fields picked with `fields=`, `None` when all the fields are answered
+    pub fn names(&self) -> Option<&[String]> {
+        self.names.as_deref()
+    }
+
+    /// Whether the field `name` is answered
+    pub fn contains(&self, name: &str) -> bool {
+        match &self.names {
+            Some(names) => names.iter().any(|field| field == name),
+            None => true,
+        }
+    }
+
+    /// The fields of the nested object `name` picked with `fields[name]=`, `None` when all its fields are answered
+    pub fn nested(&self, name: &str) -> Option<&[String]> {
+        self.nested.get(name).map(Vec::as_slice)
+    }
+
+    /// Keeps the requested fields of an object, and of each object of an array
+    pub fn project(&self, value: Value) -> Value {
+        if self.is_empty() {
+            return value;
+        }
+        match value {
+            Value::Object(object) => {
+                let object = object
+                    .into_iter()
+                    .filter(|(name, _)| self.contains(name))
+                    .map(|(name, value)| match self.nested(&name) {
+                        Some(nested) => (name, pick(nested, value)),
+                        None => (name, value),
+                    })
+                    .collect();
+                Value::Object(object)
+            }
+            Value::Array(items) => Value::Array(items.into_iter().map(|item| self.project(item)).collect()),
+            value => value,
+        }
+    }
+
+    /// The requested fields of the JSON serialization of `value`
+    pub fn project_value<T: Serialize + ?Sized>(&self, value: &T) -> Result<Value, RestError> {
+        serde_json::to_value(value)
+            .map(|value| self.project(value))
+            .map_err(|err| RestError::Internal(err.into()))
+    }
+}
+
+/// Keeps the fields `names` of a nested object, or of each object of a nested array
+fn pick(names: &[String], value: Value) -> Value {
+    match value {
+        Value::Object(object) => {
+            let object: Map<String, Value> =
+                object.into_iter().filter(|(name, _)| names.iter().any(|field| field == name)).collect();
+            Value::Object(object)
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(|item| pick(names, item)).collect()),
+        value => value,
+    }
+}
+
+/// Answers 200 OK with the requested fields of `value`, see `Fields`
+pub fn projected_response<T: Serialize + ?Sized>(req: &HttpRequest, fields: &Fields, value: &T) -> HttpResponse {
+    if fields.is_empty() {
+        return json_response(req, StatusCode::OK, value);
+    }
+    match fields.project_value(value) {
+        Ok(value) => json_response(req, StatusCode::OK, &value),
+        Err(err) => err.to_response(req),
+    }
+}
@@ -0,0 +1,306 @@
+use crate::query::deserialize_params;
+use serde::de::{Deserialize, Deserializer};
+use serde::Serialize;
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// The models whose lists can be filtered with a `FilterQuery`, by the fields they allow.
+///
+/// It is implemented by the `filter` parameter of `actix_restful_info` or `restful`,
+/// the kind of each field being read from its type, or given after its name for the type aliases.
+/// The fields are named as serde serializes them, e.g. `createdAt` with `#[serde(rename_all = "camelCase")]` :
+///
+/// ```ignore
+/// #[actix_restful_info(scope = "/v1", path = "issue", filter = "id: integer, status, created_at, tag")]
+/// struct Issue {
+///     id: Id,
+///     status: String,
+///     created_at: DateTime<Utc>,
+///     tag: Vec<String>,
+/// }
+/// ```
+pub trait Filterable {
+    fn filter_fields() -> &'static [FilterField];
+}
+
+/// A field which can be filtered on, see `Filterable`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FilterField {
+    pub name: &'static str,
+    pub kind: FilterKind,
+}
+
+/// The kind of the values compared to a field : the integers and the floats for the numeric types,
+/// the booleans for `bool`, the strings for any other type, e.g. the dates in RFC 3339
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FilterKind {
+    String,
+    Integer,
+    Float,
+    Boolean,
+}
+
+/// The operator of a condition, `filter[field][op]=value`, `eq` when it is omitted
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FilterOp {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    /// One of the values separated by commas
+    In,
+}
+
+impl FilterOp {
+    const ALL: [FilterOp; 7] = [
+        FilterOp::Eq,
+        FilterOp::Ne,
+        FilterOp::Gt,
+        FilterOp::Gte,
+        FilterOp::Lt,
+        FilterOp::Lte,
+        FilterOp::In,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FilterOp::Eq => "eq",
+            FilterOp::Ne => "ne",
+            FilterOp::Gt => "gt",
+            FilterOp::Gte => "gte",
+            FilterOp::Lt => "lt",
+            FilterOp::Lte => "lte",
+            FilterOp::In => "in",
+        }
+    }
+
+    fn parse(op: &str) -> Option<FilterOp> {
+        FilterOp::ALL.iter().copied().find(|known| known.as_str() == op)
+    }
+
+    /// Whether a field ordered `ordering` relatively to the value of the condition satisfies it
+    fn accepts(&self, ordering: Ordering) -> bool {
+        match self {
+            FilterOp::Eq | FilterOp::In => ordering == Ordering::Equal,
+            FilterOp::Ne => ordering != Ordering::Equal,
+            FilterOp::Gt => ordering == Ordering::Greater,
+            FilterOp::Gte => ordering != Ordering::Less,
+            FilterOp::Lt => ordering == Ordering::Less,
+            FilterOp::Lte => ordering != Ordering::Greater,
+        }
+    }
+}
+
+impl fmt::Display for FilterOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A value of a condition, parsed according to the kind of its field
+#[derive(Clone, Debug, PartialEq)]
+pub enum FilterValue {
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    /// The values of the `in` operator
+    List(Vec<FilterValue>),
+}
+
+impl FilterValue {
+    fn parse(kind: FilterKind, value: &str) -> Option<FilterValue> {
+        match kind {
+            FilterKind::String => Some(FilterValue::String(value.to_string())),
+            FilterKind::Integer => value.parse().ok().map(FilterValue::Integer),
+            FilterKind::Float => value.parse().ok().map(FilterValue::Float),
+            FilterKind::Boolean => value.parse().ok().map(FilterValue::Boolean),
+        }
+    }
+
+    /// The ordering of the JSON value of a field relatively to this value, `None` when they don't compare
+    fn compare(&self, field: &Value) -> Option<Ordering> {
+        match self {
+            FilterValue::String(value) => field.as_str().map(|field| field.cmp(value)),
+            FilterValue::Integer(value) => match field.as_i64() {
+                Some(field) => Some(field.cmp(value)),
+                None => field.as_f64()?.partial_cmp(&(*value as f64)),
+            },
+            FilterValue::Float(value) => field.as_f64()?.partial_cmp(value),
+            FilterValue::Boolean(value) => field.as_bool().map(|field| field.cmp(value)),
+            FilterValue::List(_) => None,
+        }
+    }
+}
+
+/// A condition on a field, `filter[created_at][gte]=2026-01-01`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Condition {
+    pub field: &'static str,
+    pub op: FilterOp,
+    pub value: FilterValue,
+}
+
+impl Condition {
+    /// Whether the JSON value of the field satisfies the condition, any of its items for an array
+    pub fn matches(&self, field: &Value) -> bool {
+        if let Value::Array(items) = field {
+            return items.iter().any(|item| self.matches(item));
+        }
+        match &self.value {
+            FilterValue::List(values) => values
+                .iter()
+                .any(|value| value.compare(field).is_some_and(|ordering| self.op.accepts(ordering))),
+            value => value.compare(field).is_some_and(|ordering| self.op.accepts(ordering)),
+        }
+    }
+
+    /// Parses the query string parameter `key=value`, `None` when it is not a filter
+    fn parse(fields: &[FilterField], key: &str, value: &str) -> Option<Result<Condition, String>> {
+        let rest = key.strip_prefix("filter[")?;
+        Some(Condition::parse_filter(fields, key, rest, value))
+    }
+
+    fn parse_filter(fields: &[FilterField], key: &str, rest: &str, value: &str) -> Result<Condition, String> {
+        let malformed = || format!("malformed filter `{}`, expected `filter[field]` or `filter[field][op]`", key);
+        let (name, rest) = rest.split_once(']').ok_or_else(malformed)?;
+        let op = match rest {
+            "" => "eq",
+            _ => rest.strip_prefix('[').and_then(|op| op.strip_suffix(']')).ok_or_else(malformed)?,
+        };
+        let field = fields.iter().find(|field| field.name == name).ok_or_else(|| {
+            let names: Vec<&str> = fields.iter().map(|field| field.name).collect();
+            format!("unknown filter field `{}`, expected one of `{}`", name, names.join("`, `"))
+        })?;
+        let op = FilterOp::parse(op).ok_or_else(|| {
+            let ops: Vec<&str> = FilterOp::ALL.iter().map(FilterOp::as_str).collect();
+            format!("unknown filter operator `{}`, expected one of `{}`", op, ops.join("`, `"))
+        })?;
+        if field.kind == FilterKind::Boolean && !matches!(op, FilterOp::Eq | FilterOp::Ne | FilterOp::In) {
+            return Err(format!("the boolean field `{}` can't be filtered with `{}`", name, op));
+        }
+        let parse = |value: &str| {
+            FilterValue::parse(field.kind, value).ok_or_else(|| {
+                format!("invalid value `{}` of the filter `{}`, expected {}", value, key, expected(field.kind))
+            })
+        };
+        let value = match op {
+            FilterOp::In => FilterValue::List(value.split(',').map(parse).collect::<Result<_, _>>()?),
+            _ => parse(value)?,
+        };
+        Ok(Condition { field: field.name, op, value })
+    }
+}
+
+fn expected(kind: FilterKind) -> &'static str {
+    match kind {
+        FilterKind::String => "a string",
+        FilterKind::Integer => "an integer",
+        FilterKind::Float => "a number",
+        FilterKind::Boolean => "`true` or `false`",
+    }
+}
+
+/// The conditions of the `filter` parameters of a query string, on the fields allowed by the `Filterable` model `M`.
+///
+/// `?filter[status]=open&filter[created_at][gte]=2026-01-01&filter[tag][in]=a,b` is parsed into the conditions
+/// `status eq "open"`, `created_at gte "2026-01-01"` and `tag in ["a", "b"]`, for the `list` implementations
+/// to translate to SQL, or to apply in memory with `matches`. A filter on an unknown field, with an unknown
+/// operator, or with a value which does not parse is answered `400 Bad Request`.
+///
+/// E.g. flattened in the list query of a model :
+///
+/// ```
+/// use actix_restful::{FilterField, FilterKind, FilterOp, FilterQuery, FilterValue, Filterable};
+///
+/// struct Issue;
+///
+/// impl Filterable for Issue {
+///     fn filter_fields() -> &'static [FilterField] {
+///         &[
+///             FilterField { name: "status", kind: FilterKind::String },
+///             FilterField { name: "priority", kind: FilterKind::Integer },
+///         ]
+///     }
+/// }
+///
+/// #[derive(serde::Deserialize)]
+/// struct ListQuery {
+///     limit: usize,
+///     #[serde(flatten)]
+///     filter: FilterQuery<Issue>,
+/// }
+///
+/// let query = actix_web::web::Query::<ListQuery>::from_query("limit=10&filter[priority][in]=1,2").unwrap();
+/// let condition = &query.filter.conditions()[0];
+/// assert_eq!((condition.field, condition.op), ("priority", FilterOp::In));
+/// assert_eq!(condition.value, FilterValue::List(vec![FilterValue::Integer(1), FilterValue::Integer(2)]));
+///
+/// assert!(actix_web::web::Query::<ListQuery>::from_query("limit=10&filter[title]=a").is_err());
+/// ```
+pub struct FilterQuery<M> {
+    conditions: Vec<Condition>,
+    model: PhantomData<fn() -> M>,
+}
+
+impl<M> FilterQuery<M> {
+    pub fn conditions(&self) -> &[Condition] {
+        &self.conditions
+    }
+
+    /// The conditions on the field `name`
+    pub fn conditions_on<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Condition> + 'a {
+        self.conditions.iter().filter(move |condition| condition.field == name)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.conditions.is_empty()
+    }
+
+    /// Whether the JSON serialization of `item` satisfies all the conditions
+    pub fn matches<T: Serialize>(&self, item: &T) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+        match serde_json::to_value(item) {
+            Ok(item) => self
+                .conditions
+                .iter()
+                .all(|condition| condition.matches(item.get(condition.field).unwrap_or(&Value::Null))),
+            Err(_) => false,
+        }
+    }
+}
+
+impl<M> Default for FilterQuery<M> {
+    fn default() -> Self {
+        FilterQuery { conditions: Vec::new(), model: PhantomData }
+    }
+}
+
+impl<M> Clone for FilterQuery<M> {
+    fn clone(&self) -> Self {
+        FilterQuery { conditions: self.conditions.clone(), model: PhantomData }
+    }
+}
+
+impl<M> fmt::Debug for FilterQuery<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FilterQuery").field("conditions", &self.conditions).finish()
+    }
+}
+
+impl<'de, M: Filterable> Deserialize<'de> for FilterQuery<M> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_params(deserializer, FilterQuery::default(), |query: &mut FilterQuery<M>, key, value| {
+            if let Some(condition) = Condition::parse(M::filter_fields(), &key, &value) {
+                query.conditions.push(condition?);
+            }
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,257 @@
+use crate::{FieldError, RestError};
+use actix_web::dev::Payload;
+use actix_web::error::PayloadError;
+use actix_web::{web, FromRequest, HttpMessage, HttpRequest};
+use serde::de::DeserializeOwned;
+use std::future::Future;
+use std::ops::Deref;
+use std::pin::Pin;
+
+/// The default limit of the size of the bodies, 256 KiB, see `RestfulPathInfo::body_limit`
+pub const DEFAULT_BODY_LIMIT: usize = 262_144;
+
+/// The raw body of a request, read within the limit of the `web::PayloadConfig` of its resource.
+///
+/// Unlike `web::Bytes`, a body over the limit is answered with a `413 Payload Too Large` problem document.
+pub struct Body(pub web::Bytes);
+
+impl Body {
+    pub fn into_inner(self) -> web::Bytes {
+        self.0
+    }
+}
+
+impl Deref for Body {
+    type Target = web::Bytes;
+
+    fn deref(&self) -> &web::Bytes {
+        &self.0
+    }
+}
+
+impl FromRequest for Body {
+    type Config = ();
+    type Error = actix_web::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, actix_web::Error>>>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let req = req.clone();
+        let body = web::Bytes::from_request(&req, payload);
+        Box::pin(async move {
+            match body.await {
+                Ok(body) => Ok(Body(body)),
+                Err(err) => {
+                    let rest_err = match err.as_error::<PayloadError>() {
+                        Some(PayloadError::Overflow) => {
+                            RestError::PayloadTooLarge(String::from("the body exceeds the limit of the resource"))
+                        }
+                        _ => RestError::BadRequest(err.to_string()),
+                    };
+                    Err(rest_err.into_error(&req))
+                }
+            }
+        })
+    }
+}
+
+/// A JSON body deserialized into `T`, the payload extractor of the generated `POST` handlers.
+///
+/// The body is read as a `Body`, and a body which does not deserialize is answered `400 Bad Request`
+/// with the JSON Pointer of the faulty value, see `parse_json`.
+pub struct JsonBody<T>(pub T);
+
+impl<T> JsonBody<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for JsonBody<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: DeserializeOwned + 'static> FromRequest for JsonBody<T> {
+    type Config = ();
+    type Error = actix_web::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, actix_web::Error>>>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let req = req.clone();
+        let body = Body::from_request(&req, payload);
+        Box::pin(async move {
+            let body = body.await?;
+            json_payload(&req, &body).map(JsonBody).map_err(|err| err.into_error(&req))
+        })
+    }
+}
+
+/// Deserializes the JSON body of `req`, answering `415 Unsupported Media Type` when its content type
+/// is not `application/json` or a `+json` type
+pub fn json_payload<T: DeserializeOwned>(req: &HttpRequest, body: &[u8]) -> Result<T, RestError> {
+    let json = match req.mime_type() {
+        Ok(Some(mime)) => mime.subtype() == "json" || mime.suffix().is_some_and(|suffix| suffix == "json"),
+        _ => false,
+    };
+    if !json {
+        return Err(RestError::UnsupportedMediaType(String::from("expected an application/json body")));
+    }
+    parse_json(body)
+}
+
+/// Deserializes a JSON document, failing with a `RestError::InvalidBody` locating the error :
+/// the JSON Pointer of the faulty value, the type expected there, and the line and column of the error.
+///
+/// ```
+/// use actix_restful::{parse_json, RestError};
+///
+/// #[derive(serde::Deserialize)]
+/// struct Item {
+///     tags: Vec<String>,
+/// }
+///
+/// match parse_json::<Item>(br#"{ "tags": ["a", 2] }"#) {
+///     Err(RestError::InvalidBody(err)) => {
+///         assert_eq!(err.field, "/tags/1");
+///         assert_eq!(err.code, "invalid-type");
+///         assert_eq!(err.expected.as_deref(), Some("a string"));
+///         assert_eq!((err.line, err.column), (Some(1), Some(17)));
+///     }
+///     _ => panic!("expected an invalid body"),
+/// }
+/// ```
+pub fn parse_json<T: DeserializeOwned>(body: &[u8]) -> Result<T, RestError> {
+    serde_json::from_slice(body).map_err(|err| RestError::InvalidBody(Box::new(body_error(body, &err))))
+}
+
+/// The codes of the data errors, from the prefix of the serde message
+const DATA_ERRORS: [(&str, &str); 7] = [
+    ("missing field", "missing-field"),
+    ("unknown field", "unknown-field"),
+    ("duplicate field", "duplicate-field"),
+    ("invalid type", "invalid-type"),
+    ("invalid value", "invalid-value"),
+    ("invalid length", "invalid-length"),
+    ("unknown variant", "unknown-variant"),
+];
+
+fn body_error(body: &[u8], err: &serde_json::Error) -> FieldError {
+    let (line, column) = (err.line(), err.column());
+    let message = err.to_string();
+    let message = message
+        .strip_suffix(&format!(" at line {} column {}", line, column))
+        .unwrap_or(&message)
+        .to_string();
+    let mut field = pointer_at(body, offset_of(body, line, column));
+    // the missing field of an object is reported at the end of the object
+    if let Some(missing) = message.strip_prefix("missing field `").and_then(|rest| rest.strip_suffix('`')) {
+        field = format!("{}/{}", field, escape_token(missing));
+    }
+    FieldError {
+        line: Some(line),
+        column: Some(column),
+        ..data_error(field, err, message)
+    }
+}
+
+/// The error of the value at the JSON Pointer `field` of a document already parsed, which does not deserialize
+pub(crate) fn value_error(field: &str, err: &serde_json::Error) -> FieldError {
+    data_error(field.to_string(), err, err.to_string())
+}
+
+fn data_error(field: String, err: &serde_json::Error, message: String) -> FieldError {
+    let code = match err.classify() {
+        serde_json::error::Category::Data => DATA_ERRORS
+            .iter()
+            .find(|(prefix, _)| message.starts_with(prefix))
+            .map_or("invalid-data", |(_, code)| code),
+        _ => "invalid-json",
+    };
+    FieldError {
+        field,
+        code: code.to_string(),
+        expected: message.split_once(", expected ").map(|(_, expected)| expected.to_string()),
+        message,
+        line: None,
+        column: None,
+    }
+}
+
+/// The byte offset of the `column` of the `line` reported by serde_json, both starting at 1
+fn offset_of(body: &[u8], line: usize, column: usize) -> usize {
+    let start = match line {
+        0 | 1 => 0,
+        _ => body
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| **b == b'\n')
+            .nth(line - 2)
+            .map_or(body.len(), |(index, _)| index + 1),
+    };
+    (start + column).min(body.len())
+}
+
+/// A container of the document, opened before the offset of the error
+enum Frame {
+    Object { key: Option<String>, expecting_key: bool },
+    Array { index: usize },
+}
+
+/// The JSON Pointer (RFC 6901) of the value being read at `offset` in the document `body`
+fn pointer_at(body: &[u8], offset: usize) -> String {
+    let body = &body[..offset];
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut index = 0;
+    while index < body.len() {
+        match body[index] {
+            b'{' => stack.push(Frame::Object { key: None, expecting_key: true }),
+            b'[' => stack.push(Frame::Array { index: 0 }),
+            b'}' | b']' => {
+                stack.pop();
+            }
+            b',' => match stack.last_mut() {
+                Some(Frame::Object { key, expecting_key }) => {
+                    *key = None;
+                    *expecting_key = true;
+                }
+                Some(Frame::Array { index }) => *index += 1,
+                None => {}
+            },
+            b':' => {
+                if let Some(Frame::Object { expecting_key, .. }) = stack.last_mut() {
+                    *expecting_key = false;
+                }
+            }
+            b'"' => {
+                let start = index;
+                index += 1;
+                while index < body.len() && body[index] != b'"' {
+                    index += if body[index] == b'\\' { 2 } else { 1 };
+                }
+                if let (Some(Frame::Object { key, expecting_key: true }), Some(string)) =
+                    (stack.last_mut(), body.get(start..=index))
+                {
+                    *key = serde_json::from_slice(string).ok();
+                }
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+    stack
+        .iter()
+        .filter_map(|frame| match frame {
+            Frame::Object { key: Some(key), .. } => Some(format!("/{}", escape_token(key))),
+            Frame::Object { key: None, .. } => None,
+            Frame::Array { index } => Some(format!("/{}", index)),
+        })
+        .collect()
+}
+
+/// Escapes a reference token of a JSON Pointer
+fn escape_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
@@ -1,13 +1,13 @@
 //!
 //! # Example of use :
-//! ```
+//! ```no_run
 //!use serde::{Serialize, Deserialize};
 //!use actix_restful::{
-//!     HttpCreate,
-//!     HttpFindListDelete,
-//!     HttpUpdate,
-//!     Model,
+//!     Deletable,
+//!     Findable,
+//!     Listable,
 //!     NewModel,
+//!     RequestContext,
 //!     UpdatableModel,
 //!     gen_endpoint,
 //!     RestfulPathInfo
@@ -17,7 +17,6 @@
 //!use async_trait::async_trait;
 //!use std::default::Default;
 //!use actix_web;
-//!use serde_json;
 //!use chrono::prelude::*;
 //!
 //!struct AppState {}
@@ -36,7 +35,6 @@
 //!type Id = i64;
 //!
 //!#[derive(Default, Serialize, Deserialize, HttpFindListDelete)]
-//!#[http_find_list_delete(Id, FindQuery, ListQuery, DeleteQuery, AppState)]
 //!#[actix_restful_info(scope = "/v1", path = "item")]
 //!struct Item {
 //!    id: Id,
@@ -47,8 +45,12 @@
 //!}
 //!
 //!#[async_trait]
-//!impl Model<Id, FindQuery, ListQuery, ListResult, DeleteQuery, DeleteResult, AppState> for Item {
-//!    async fn find(id: Id, _query: &FindQuery, _state: &AppState) -> Result<Box<Item>> {
+//!impl Findable for Item {
+//!    type Id = Id;
+//!    type FindQuery = FindQuery;
+//!    type State = AppState;
+//!
+//!    async fn find(id: Id, _query: &FindQuery, _state: &AppState, _ctx: &RequestContext) -> Result<Box<Item>> {
 //!        // fetch from somwhere with id and return result
 //!        Ok(
 //!            Box::new(
@@ -62,7 +64,15 @@
 //!            )
 //!        )
 //!    }
-//!    async fn list(_query: &ListQuery, _state: &AppState) -> Result<ListResult> {
+//!}
+//!
+//!#[async_trait]
+//!impl Listable for Item {
+//!    type ListQuery = ListQuery;
+//!    type ListResult = ListResult;
+//!    type State = AppState;
+//!
+//!    async fn list(_query: &ListQuery, _state: &AppState, _ctx: &RequestContext) -> Result<ListResult> {
 //!        // list
 //!        let mut res = Vec::new();
 //!        for i in 0..2{
@@ -76,7 +86,14 @@
 //!        }
 //!        Ok(res)
 //!    }
-//!    async fn delete(mut self: Self, _query: &DeleteQuery, _state: &AppState) -> Result<DeleteResult> {
+//!}
+//!
+//!#[async_trait]
+//!impl Deletable for Item {
+//!    type DeleteQuery = DeleteQuery;
+//!    type DeleteResult = DeleteResult;
+//!
+//!    async fn delete(mut self: Self, _query: &DeleteQuery, _state: &AppState, _ctx: &RequestContext) -> Result<DeleteResult> {
 //!        // hard or soft delete
 //!        let utc: DateTime<Utc> = Utc::now();
 //!        self.deleted_at = Some(utc);
@@ -85,13 +102,16 @@
 //!}
 //!
 //!#[derive(Serialize, Deserialize, HttpCreate)]
-//!#[http_create(SaveQuery, AppState)]
 //!struct NewItem {
 //!     content: String,
 //!}
 //!#[async_trait]
-//!impl NewModel<Item, SaveQuery, AppState> for NewItem {
-//!     async fn save(self: Self, _query: &SaveQuery, _state: &AppState) -> Result<Item> {
+//!impl NewModel for NewItem {
+//!     type Output = Item;
+//!     type Query = SaveQuery;
+//!     type State = AppState;
+//!
+//!     async fn save(self, _query: &SaveQuery, _state: &AppState, _ctx: &RequestContext) -> Result<Item> {
 //!         // persist, and return Item entity
 //!         let utc: DateTime<Utc> = Utc::now();
 //!         Ok(Item{
@@ -105,15 +125,19 @@
 //!}
 //!
 //!#[derive(Serialize, Deserialize, HttpUpdate)]
-//!#[http_update(Id, UpdateQuery, Item, FindQuery, AppState)]
 //!struct UpdatableItem {
 //!     id: Id,
 //!     content: String,
 //!     updated_at: Option<DateTime<Utc>>,
 //!}
 //!#[async_trait]
-//!impl UpdatableModel<UpdatableItem, UpdateQuery, AppState> for UpdatableItem {
-//!     async fn update(mut self: Self, _query: &UpdateQuery, _state: &AppState) -> Result<UpdatableItem> {
+//!impl UpdatableModel for UpdatableItem {
+//!     type Model = Item;
+//!     type Output = UpdatableItem;
+//!     type Query = UpdateQuery;
+//!     type State = AppState;
+//!
+//!     async fn update(mut self: Self, _id: Id, _current: Item, _query: &UpdateQuery, _state: &AppState, _ctx: &RequestContext) -> Result<UpdatableItem> {
 //!         // update in db
 //!         let utc: DateTime<Utc> = Utc::now();
 //!         self.updated_at = Some(utc);
@@ -133,159 +157,781 @@
 //!     .await
 //!}
 //! ```
+//!
+//! # Query strings
+//!
+//! `FilterQuery`, `SortQuery`, `PageQuery` and `CursorQuery` read their own parameters of the query string, the other
+//! parameters being ignored. Each can be the list query of a model, or a flattened field of it, along with the others :
+//!
+//! ```no_run
+//! # use actix_restful::{FilterQuery, PageQuery, SortQuery};
+//! # use serde::Deserialize;
+//! # #[actix_restful_derive::actix_restful_info(scope = "/v1", path = "item", filter = "status", sort = "id")]
+//! # struct Item {
+//! #     id: i64,
+//! #     status: String,
+//! # }
+//! #[derive(Deserialize)]
+//! struct ListQuery {
+//!     #[serde(flatten)]
+//!     filter: FilterQuery<Item>,
+//!     #[serde(flatten)]
+//!     sort: SortQuery<Item>,
+//!     #[serde(flatten)]
+//!     page: PageQuery,
+//! }
+//! ```
 
-use actix_web::{web, HttpResponse};
+use actix_web::{web, HttpRequest, HttpResponse};
 use anyhow::Result;
-use async_trait::async_trait;
 
-/// A trait to implement on your main struct entity via the HttpFindListDelete derive macro :
+#[cfg(feature = "compat")]
+pub mod compat;
+mod config;
+mod context;
+mod cursor;
+mod error;
+mod fields;
+mod filter;
+mod json;
+mod pagination;
+mod patch;
+mod path;
+mod problem;
+mod query;
+mod resource;
+mod response;
+mod sort;
+mod update;
+mod validate;
+
+pub use config::RestfulConfig;
+pub use context::{ContextExtensions, RequestContext};
+pub use cursor::{cursor_response, CursorPage, CursorQuery};
+pub use error::RestError;
+pub use fields::{projected_response, Fields};
+pub use filter::{Condition, FilterField, FilterKind, FilterOp, FilterQuery, FilterValue, Filterable};
+pub use json::{json_payload, parse_json, Body, JsonBody, DEFAULT_BODY_LIMIT};
+pub use pagination::{paginated_response, PageQuery, Paginated};
+pub use path::{path_id, path_param, IdPath, ParentLookup};
+pub use patch::{
+    apply_patch, json_patch, merge_patch, PatchOperation, JSON_PATCH_JSON, MERGE_PATCH_JSON,
+};
+pub use problem::{configure_extractors, Problem, PROBLEM_JSON};
+pub use query::NoQuery;
+pub use resource::{
+    CreateRoute, DeleteRoute, FindRoute, ListRoute, PatchRoute, Resource, RestfulRoutes, UpdateRoute,
+};
+pub use response::{created_response, json_response};
+pub use sort::{SortDirection, SortField, SortQuery, Sortable};
+pub use update::{check_body_id, update_payload};
+pub use validate::{
+    validate_email, validate_length, validate_range, FieldError, Length, Validate, ValidationErrors,
+};
+#[doc(hidden)]
+pub use async_trait::async_trait;
+#[doc(hidden)]
+pub use pagination::{CursorListingKind, Listing, ListingKind, PaginatedListing, PlainListing, PlainListingKind};
+#[doc(hidden)]
+pub use validate::{SkipValidation, ValidatePayload, Validation};
+
+/// A trait to implement on your main struct entity to answer `GET /{path}/{id}`,
+/// via the HttpFind or HttpFindListDelete derive macros :
 ///
-/// ```
+/// The methods of the model traits may fail with a `RestError` to choose the status code of the response,
+/// e.g. `RestError::NotFound` in `find` answers 404. Any other error answers 500.
+/// They all receive the `RequestContext` of the request last, to read its headers or extensions.
 ///
+/// ```no_run
 /// use actix_restful::{
-///  HttpFindListDelete,
-///  Model,
-///  RestfulPathInfo
+///  Deletable,
+///  Findable,
+///  Listable,
+///  NoQuery,
+///  RequestContext,
+///  RestError,
 /// };
 /// use actix_restful_derive::{HttpFindListDelete, actix_restful_info};
 /// use anyhow::Result;
 /// use async_trait::async_trait;
+/// use serde::Serialize;
+/// # struct AppState {}
+/// # type ListQuery = NoQuery;
 ///
-/// #[derive(HttpFindListDelete)]
-/// #[http_find_list_delete(Id, FindQuery, ListQuery, DeleteQuery, AppState)]
+/// #[derive(Serialize, HttpFindListDelete)]
 /// #[actix_restful_info(scope = "/v1", path = "item")]
 /// struct Item {
+///     id: i64,
 /// }
 ///
 /// #[async_trait]
-/// impl Model<Id, FindQuery, ListQuery, ListResult, DeleteQuery, DeleteResult, AppState> for Item {
-///    async fn find(id: Id, _query: &FindQuery, _state: &AppState) -> Result<Box<Item>> {
+/// impl Findable for Item {
+///    type Id = i64;
+///    type FindQuery = NoQuery;
+///    type State = AppState;
+///
+///    async fn find(id: i64, _query: &NoQuery, _state: &AppState, _ctx: &RequestContext) -> Result<Box<Item>> {
+///        match id {
+///            1 => Ok(Box::new(Item { id })),
+///            _ => Err(RestError::NotFound(format!("no item {}", id)).into()),
+///        }
 ///    }
-///    async fn list(_query: &ListQuery, _state: &AppState) -> Result<ListResult> {
+/// }
+///
+/// #[async_trait]
+/// impl Listable for Item {
+///    type ListQuery = ListQuery;
+///    type ListResult = Vec<Item>;
+///    type State = AppState;
+///
+///    async fn list(_query: &ListQuery, _state: &AppState, _ctx: &RequestContext) -> Result<Vec<Item>> {
+///        Ok(vec![Item { id: 1 }])
 ///    }
-///    async fn delete(mut self: Self, _query: &DeleteQuery, _state: &AppState) -> Result<DeleteResult> {
+/// }
+///
+/// #[async_trait]
+/// impl Deletable for Item {
+///    type DeleteQuery = NoQuery;
+///    type DeleteResult = Item;
+///
+///    async fn delete(self, _query: &NoQuery, _state: &AppState, _ctx: &RequestContext) -> Result<Item> {
+///        Ok(self)
 ///    }
 /// }
 /// ```
+///
+/// The derives read the types of the handlers from these associated types. A model implements only
+/// the traits of the operations it supports, e.g. `Findable` and `Listable` for an append-only resource,
+/// with the `HttpFind` and `HttpList` derives.
+///
+/// The model traits of the previous versions, `Model<ID, FQ, LQ, LR, DQ, DR, AppState>` and the like,
+/// are available in the `compat` module with the `compat` feature, selected per derive with its `compat` flag.
 #[async_trait]
-pub trait Model<ID, FQ, LQ, LR, DQ, DR, AppState> {
-    async fn find(id: ID, query: &FQ, state: &AppState) -> Result<Box<Self>>;
-    async fn list(query: &LQ, state: &AppState) -> Result<LR>;
-    async fn delete(self: Self, query: &DQ, state: &AppState) -> Result<DR>;
+pub trait Findable: Sized {
+    type Id;
+    /// The query string of `GET /{path}/{id}`, its `Default` is handed to `find` by the other handlers
+    type FindQuery;
+    type State;
+
+    async fn find(
+        id: Self::Id,
+        query: &Self::FindQuery,
+        state: &Self::State,
+        ctx: &RequestContext,
+    ) -> Result<Box<Self>>;
 }
 
-/// A trait to implement on your creatable entity entity
+/// A trait to implement on your main struct entity to answer `GET /{path}`, see `Findable`
+#[async_trait]
+pub trait Listable: Sized {
+    type ListQuery;
+    type ListResult;
+    type State;
+
+    async fn list(
+        query: &Self::ListQuery,
+        state: &Self::State,
+        ctx: &RequestContext,
+    ) -> Result<Self::ListResult>;
+}
+
+/// A trait to implement on your main struct entity to answer `DELETE /{path}/{id}`, see `Findable`.
 ///
+/// The entity to delete is loaded with `Findable::find`.
+#[async_trait]
+pub trait Deletable: Findable {
+    type DeleteQuery;
+    type DeleteResult;
+
+    async fn delete(
+        self,
+        query: &Self::DeleteQuery,
+        state: &Self::State,
+        ctx: &RequestContext,
+    ) -> Result<Self::DeleteResult>;
+}
+
+/// The counterpart of `Findable` for a model nested under a parent model :
+///
+/// ```no_run
+/// # use actix_restful::{Findable, NestedDeletable, NestedFindable, NestedListable, NoQuery, RequestContext};
+/// # use actix_restful_derive::{actix_restful_info, HttpFind, HttpFindListDelete};
+/// # use anyhow::Result;
+/// # use async_trait::async_trait;
+/// # use serde::Serialize;
+/// # struct AppState {}
+/// # type ProjectId = i64;
+/// # #[derive(Serialize, HttpFind)]
+/// # #[actix_restful_info(scope = "/v1", path = "project")]
+/// # struct Project {
+/// #     id: ProjectId,
+/// # }
+/// # #[async_trait]
+/// # impl Findable for Project {
+/// #     type Id = ProjectId;
+/// #     type FindQuery = NoQuery;
+/// #     type State = AppState;
+/// #     async fn find(id: ProjectId, _query: &NoQuery, _state: &AppState, _ctx: &RequestContext) -> Result<Box<Project>> {
+/// #         Ok(Box::new(Project { id }))
+/// #     }
+/// # }
+/// #[derive(Serialize, HttpFindListDelete)]
+/// #[actix_restful_info(scope = "/v1", path = "task", parent = "Project")]
+/// struct Task {
+///     id: i64,
+///     project_id: ProjectId,
+/// }
+///
+/// #[async_trait]
+/// impl NestedFindable for Task {
+///    type ParentId = ProjectId;
+///    type Id = i64;
+///    type FindQuery = NoQuery;
+///    type State = AppState;
+///
+///    async fn find(project_id: ProjectId, id: i64, _query: &NoQuery, _state: &AppState, _ctx: &RequestContext) -> Result<Box<Task>> {
+///        Ok(Box::new(Task { id, project_id }))
+///    }
+/// }
+///
+/// #[async_trait]
+/// impl NestedListable for Task {
+///    type ParentId = ProjectId;
+///    type ListQuery = NoQuery;
+///    type ListResult = Vec<Task>;
+///    type State = AppState;
+///
+///    async fn list(project_id: ProjectId, _query: &NoQuery, _state: &AppState, _ctx: &RequestContext) -> Result<Vec<Task>> {
+///        Ok(vec![Task { id: 1, project_id }])
+///    }
+/// }
+///
+/// #[async_trait]
+/// impl NestedDeletable for Task {
+///    type DeleteQuery = NoQuery;
+///    type DeleteResult = Task;
+///
+///    async fn delete(self, _project_id: ProjectId, _query: &NoQuery, _state: &AppState, _ctx: &RequestContext) -> Result<Task> {
+///        Ok(self)
+///    }
+/// }
 /// ```
 ///
+/// The routes are `/v1/project/{project_id}/task` and `/v1/project/{project_id}/task/{id}`.
+/// The handlers call `find` on the parent first, and answer its error when it fails,
+/// so return a `RestError::NotFound` from the parent `find` to answer 404.
+#[async_trait]
+pub trait NestedFindable: Sized {
+    type ParentId;
+    type Id;
+    type FindQuery;
+    type State;
+
+    async fn find(
+        parent_id: Self::ParentId,
+        id: Self::Id,
+        query: &Self::FindQuery,
+        state: &Self::State,
+        ctx: &RequestContext,
+    ) -> Result<Box<Self>>;
+}
+
+/// The counterpart of `Listable` for a model nested under a parent model, see `NestedFindable`
+#[async_trait]
+pub trait NestedListable: Sized {
+    type ParentId;
+    type ListQuery;
+    type ListResult;
+    type State;
+
+    async fn list(
+        parent_id: Self::ParentId,
+        query: &Self::ListQuery,
+        state: &Self::State,
+        ctx: &RequestContext,
+    ) -> Result<Self::ListResult>;
+}
+
+/// The counterpart of `Deletable` for a model nested under a parent model, see `NestedFindable`
+#[async_trait]
+pub trait NestedDeletable: NestedFindable {
+    type DeleteQuery;
+    type DeleteResult;
+
+    async fn delete(
+        self,
+        parent_id: Self::ParentId,
+        query: &Self::DeleteQuery,
+        state: &Self::State,
+        ctx: &RequestContext,
+    ) -> Result<Self::DeleteResult>;
+}
+
+/// A trait to implement on your creatable entity entity
+///
+/// `save` may fail with a `RestError`, e.g. `RestError::Conflict` on a uniqueness violation answers 409.
+///
+/// ```no_run
 /// use actix_restful::{
-///  HttpCreate,
 ///  NewModel,
+///  NoQuery,
+///  RequestContext,
 /// };
 /// use actix_restful_derive::HttpCreate;
 /// use anyhow::Result;
 /// use async_trait::async_trait;
+/// use serde::Deserialize;
+/// # struct AppState {}
+/// # type SaveQuery = NoQuery;
+/// # #[derive(serde::Serialize)]
+/// # #[actix_restful_derive::actix_restful_info(scope = "/v1", path = "item")]
+/// # struct Item {
+/// #     id: i64,
+/// #     content: String,
+/// # }
 ///
-/// #[derive(HttpCreate)]
-/// #[http_create(SaveQuery, AppState)]
+/// #[derive(Deserialize, HttpCreate)]
 /// struct NewItem {
+///     content: String,
 /// }
 ///
 /// #[async_trait]
-/// impl NewModel<Item, SaveQuery, AppState> for NewItem {
-///    async fn save(self: Self, _query: &SaveQuery, _state: &AppState) -> Result<Item> {
+/// impl NewModel for NewItem {
+///    type Output = Item;
+///    type Query = SaveQuery;
+///    type State = AppState;
+///
+///    async fn save(self, _query: &SaveQuery, _state: &AppState, _ctx: &RequestContext) -> Result<Item> {
+///        Ok(Item { id: 1, content: self.content })
 ///    }
 /// }
 /// ```
 #[async_trait]
-pub trait NewModel<T, Q, AppState> {
-    async fn save(self: Self, query: &Q, state: &AppState) -> Result<T>;
+pub trait NewModel {
+    /// The saved entity, answered with a `Location` header
+    type Output;
+    type Query;
+    type State;
+
+    async fn save(
+        self,
+        query: &Self::Query,
+        state: &Self::State,
+        ctx: &RequestContext,
+    ) -> Result<Self::Output>;
+}
+
+/// The counterpart of `NewModel` for a model nested under a parent model,
+/// derived with `#[http_create(parent = Project)]`
+#[async_trait]
+pub trait NestedNewModel {
+    type ParentId;
+    type Output;
+    type Query;
+    type State;
+
+    async fn save(
+        self,
+        parent_id: Self::ParentId,
+        query: &Self::Query,
+        state: &Self::State,
+        ctx: &RequestContext,
+    ) -> Result<Self::Output>;
 }
 
 /// A trait to implement on your Updatable entity
 ///
-/// ```
+/// `update` receives the id of the path and the entity it replaces, loaded with `Findable::find`.
+/// A body whose `id` differs from the id of the path is answered 409 Conflict before `update` is called,
+/// see `check_body_id`. `update` may fail with a `RestError` to choose the status code of the response.
 ///
+/// ```no_run
 /// use actix_restful::{
-///  HttpUpdate,
+///  NoQuery,
+///  RequestContext,
 ///  UpdatableModel,
 /// };
 /// use actix_restful_derive::HttpUpdate;
 /// use anyhow::Result;
 /// use async_trait::async_trait;
+/// use serde::{Deserialize, Serialize};
+/// # struct AppState {}
+/// # type UpdateQuery = NoQuery;
+/// # #[actix_restful_derive::actix_restful_info(scope = "/v1", path = "item")]
+/// # struct Item {
+/// #     id: i64,
+/// #     revision: u32,
+/// # }
+/// # #[async_trait]
+/// # impl actix_restful::Findable for Item {
+/// #     type Id = i64;
+/// #     type FindQuery = NoQuery;
+/// #     type State = AppState;
+/// #     async fn find(id: i64, _query: &NoQuery, _state: &AppState, _ctx: &RequestContext) -> Result<Box<Item>> {
+/// #         Ok(Box::new(Item { id, revision: 1 }))
+/// #     }
+/// # }
 ///
-/// #[derive(HttpUpdate)]
-/// #[http_update(Id, UpdateQuery, Item, FindQuery, AppState)]
+/// #[derive(Serialize, Deserialize, HttpUpdate)]
 /// struct UpdatableItem {
+///     id: i64,
+///     content: String,
+///     revision: u32,
 /// }
 ///
 /// #[async_trait]
-/// impl UpdatableModel<UpdatableItem, UpdateQuery, AppState> for UpdatableItem {
-///     async fn update(mut self: Self, _query: &UpdateQuery, _state: &AppState) -> Result<UpdatableItem> {
+/// impl UpdatableModel for UpdatableItem {
+///     type Model = Item;
+///     type Output = UpdatableItem;
+///     type Query = UpdateQuery;
+///     type State = AppState;
+///
+///     async fn update(mut self, _id: i64, current: Item, _query: &UpdateQuery, _state: &AppState, _ctx: &RequestContext) -> Result<UpdatableItem> {
+///         self.revision = current.revision + 1;
+///         Ok(self)
 ///     }
 /// }
 /// ```
 #[async_trait]
-pub trait UpdatableModel<T, Q, AppState> {
-    async fn update(self: Self, query: &Q, state: &AppState) -> Result<T>;
+pub trait UpdatableModel {
+    /// The model updated, the `model` of `http_update` defaults to it
+    type Model: Findable;
+    type Output;
+    type Query;
+    type State;
+
+    async fn update(
+        self,
+        id: <Self::Model as Findable>::Id,
+        current: Self::Model,
+        query: &Self::Query,
+        state: &Self::State,
+        ctx: &RequestContext,
+    ) -> Result<Self::Output>;
+}
+
+/// The counterpart of `UpdatableModel` for a model nested under a parent model,
+/// derived with `#[http_update(parent = Project)]`
+#[async_trait]
+pub trait NestedUpdatableModel {
+    type ParentId;
+    type Model: NestedFindable;
+    type Output;
+    type Query;
+    type State;
+
+    async fn update(
+        self,
+        parent_id: Self::ParentId,
+        id: <Self::Model as NestedFindable>::Id,
+        current: Self::Model,
+        query: &Self::Query,
+        state: &Self::State,
+        ctx: &RequestContext,
+    ) -> Result<Self::Output>;
 }
 
-/// This Trait is automatically implemented with the `actix_restful_derive::HttpCreate` derive macro
+/// A trait to implement on your Patchable entity
+///
+/// The PATCH body is a JSON Merge Patch (RFC 7396) document, or a JSON Patch (RFC 6902) document
+/// when sent as `application/json-patch+json`. It is applied on top of the entity loaded with
+/// `Findable::find`, and `patch` is called on the result with the id of the path. A failing JSON Patch
+/// `test` operation answers 409, as a patch changing the `id` of the entity, see `check_body_id`.
+///
+/// ```no_run
+/// use actix_restful::{
+///  NoQuery,
+///  PatchableModel,
+///  RequestContext,
+/// };
+/// use actix_restful_derive::HttpPatch;
+/// use anyhow::Result;
+/// use async_trait::async_trait;
+/// use serde::{Deserialize, Serialize};
+/// # struct AppState {}
+/// # type PatchQuery = NoQuery;
+/// # #[derive(Serialize)]
+/// # #[actix_restful_derive::actix_restful_info(scope = "/v1", path = "item")]
+/// # struct Item {
+/// #     id: i64,
+/// #     content: String,
+/// # }
+/// # #[async_trait]
+/// # impl actix_restful::Findable for Item {
+/// #     type Id = i64;
+/// #     type FindQuery = NoQuery;
+/// #     type State = AppState;
+/// #     async fn find(id: i64, _query: &NoQuery, _state: &AppState, _ctx: &RequestContext) -> Result<Box<Item>> {
+/// #         Ok(Box::new(Item { id, content: String::new() }))
+/// #     }
+/// # }
+///
+/// #[derive(Serialize, Deserialize, HttpPatch)]
+/// struct PatchableItem {
+///     id: i64,
+///     content: String,
+/// }
+///
+/// #[async_trait]
+/// impl PatchableModel for PatchableItem {
+///     type Model = Item;
+///     type Output = PatchableItem;
+///     type Query = PatchQuery;
+///     type State = AppState;
+///
+///     async fn patch(self, _id: i64, _query: &PatchQuery, _state: &AppState, _ctx: &RequestContext) -> Result<PatchableItem> {
+///         Ok(self)
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait PatchableModel {
+    /// The model patched, the `model` of `http_patch` defaults to it
+    type Model: Findable;
+    type Output;
+    type Query;
+    type State;
 
+    async fn patch(
+        self,
+        id: <Self::Model as Findable>::Id,
+        query: &Self::Query,
+        state: &Self::State,
+        ctx: &RequestContext,
+    ) -> Result<Self::Output>;
+}
+
+/// The counterpart of `PatchableModel` for a model nested under a parent model,
+/// derived with `#[http_patch(parent = Project)]`
 #[async_trait]
+pub trait NestedPatchableModel {
+    type ParentId;
+    type Model: NestedFindable;
+    type Output;
+    type Query;
+    type State;
+
+    async fn patch(
+        self,
+        parent_id: Self::ParentId,
+        id: <Self::Model as NestedFindable>::Id,
+        query: &Self::Query,
+        state: &Self::State,
+        ctx: &RequestContext,
+    ) -> Result<Self::Output>;
+}
+
+/// This Trait is automatically implemented with the `actix_restful_derive::HttpCreate` derive macro
+
+#[async_trait(?Send)]
 pub trait HttpCreate<Q, AppState> {
     /// This method is automaticaly implemented with the `actix_restful_derive::HttpCreate` derive macro
     async fn http_create(
-        payload: web::Json<Box<Self>>,
+        req: HttpRequest,
+        payload: JsonBody<Box<Self>>,
         query: web::Query<Q>,
         app_state: web::Data<AppState>,
     ) -> Result<HttpResponse, HttpResponse>;
 }
 
-/// This Trait is automatically implemented with the `actix_restful_derive::HttpFindListDelete` derive macro
+/// This Trait is automatically implemented with the `actix_restful_derive::HttpFind` derive macro
 
-#[async_trait]
-pub trait HttpFindListDelete<P, FQ, LQ, DQ, AppState> {
-    /// This method is automatically implemented with the `actix_restful_derive::HttpFindListDelete` derive macro
+#[async_trait(?Send)]
+pub trait HttpFind<P, Q, AppState> {
+    /// This method is automatically implemented with the `actix_restful_derive::HttpFind` derive macro
     async fn http_find(
-        info: web::Path<P>,
-        query: web::Query<FQ>,
+        req: HttpRequest,
+        info: P,
+        query: web::Query<Q>,
         app_state: web::Data<AppState>,
     ) -> Result<HttpResponse, HttpResponse>;
-    /// This method is automatically implemented with the `actix_restful_derive::HttpFindListDelete` derive macro
+}
+
+/// This Trait is automatically implemented with the `actix_restful_derive::HttpList` derive macro
+
+#[async_trait(?Send)]
+pub trait HttpList<Q, AppState> {
+    /// This method is automatically implemented with the `actix_restful_derive::HttpList` derive macro
     async fn http_list(
-        query: web::Query<LQ>,
+        req: HttpRequest,
+        query: web::Query<Q>,
         app_state: web::Data<AppState>,
     ) -> Result<HttpResponse, HttpResponse>;
-    /// This method is automatically implemented with the `actix_restful_derive::HttpFindListDelete` derive macro
+}
+
+/// This Trait is automatically implemented with the `actix_restful_derive::HttpDelete` derive macro
+
+#[async_trait(?Send)]
+pub trait HttpDelete<P, Q, AppState> {
+    /// This method is automatically implemented with the `actix_restful_derive::HttpDelete` derive macro
     async fn http_delete(
-        info: web::Path<P>,
-        query: web::Query<DQ>,
+        req: HttpRequest,
+        info: P,
+        query: web::Query<Q>,
         app_state: web::Data<AppState>,
     ) -> Result<HttpResponse, HttpResponse>;
 }
 
+/// The handlers of a model answering find, list and delete, implemented along with them
+/// by the `actix_restful_derive::HttpFindListDelete` derive macro
+pub trait HttpFindListDelete<P, FQ, LQ, DQ, AppState>:
+    HttpFind<P, FQ, AppState> + HttpList<LQ, AppState> + HttpDelete<P, DQ, AppState>
+{
+}
+
+impl<T, P, FQ, LQ, DQ, AppState> HttpFindListDelete<P, FQ, LQ, DQ, AppState> for T where
+    T: HttpFind<P, FQ, AppState> + HttpList<LQ, AppState> + HttpDelete<P, DQ, AppState>
+{
+}
+
 /// This Trait is automaticaly implemented with the `actix_restful_derive::HttpUpdate` derive macro
 
-#[async_trait]
+#[async_trait(?Send)]
 pub trait HttpUpdate<P, Q, AppState> {
     /// This method is automaticaly implemented with the `actix_restful_derive::HttpUpdate` derive macro
     async fn http_update(
-        info: web::Path<P>,
-        payload: web::Json<Box<Self>>,
+        req: HttpRequest,
+        info: P,
+        payload: Body,
         query: web::Query<Q>,
         app_state: web::Data<AppState>,
     ) -> Result<HttpResponse, HttpResponse>;
 }
 
+/// This Trait is automaticaly implemented with the `actix_restful_derive::HttpPatch` derive macro
+
+#[async_trait(?Send)]
+pub trait HttpPatch<P, Q, AppState> {
+    /// This method is automaticaly implemented with the `actix_restful_derive::HttpPatch` derive macro
+    async fn http_patch(
+        req: HttpRequest,
+        info: P,
+        payload: Body,
+        query: web::Query<Q>,
+        app_state: web::Data<AppState>,
+    ) -> Result<HttpResponse, HttpResponse>;
+}
+
+/// This Trait is automatically implemented with the `actix_restful_derive::actix_restful_info` attribute macro
 pub trait RestfulPathInfo {
     fn path() -> String;
     fn scope() -> &'static str;
+    /// Whether DELETE answers 204 No Content instead of the deleted entity,
+    /// set with `#[actix_restful_info(scope = "/v1", path = "item", delete_no_content)]`
+    fn delete_no_content() -> bool {
+        false
+    }
+    /// The limit of the size of the bodies of the routes of the model, in bytes, `DEFAULT_BODY_LIMIT` by default.
+    ///
+    /// Set with `#[actix_restful_info(scope = "/v1", path = "item", body_limit = 1048576)]`,
+    /// a larger body is answered 413 Payload Too Large.
+    fn body_limit() -> usize {
+        DEFAULT_BODY_LIMIT
+    }
+    /// The fields of the model which can be picked with `?fields=`, any field when it is empty.
+    ///
+    /// Set by `actix_restful_info` to the named fields of the struct as serde serializes them, or left empty when it
+    /// flattens another struct, see `Fields`.
+    fn field_names() -> &'static [&'static str] {
+        &[]
+    }
+    /// The path of the model below the scope, including the path of its parents for a nested model,
+    /// e.g. `project/{project_id}/task`
+    fn route_path() -> String {
+        Self::path()
+    }
+    /// The pattern of the id in the path of an item, `{id}` by default.
+    ///
+    /// Set with `#[actix_restful_info(scope = "/v1", path = "item", id_pattern = "{tenant}/{code}")]`
+    /// for a composite id, see `path_id`.
+    fn id_pattern() -> String {
+        String::from("{id}")
+    }
+    /// The name of the path parameter holding the id of this model in the routes of its nested models
+    fn parent_id_param() -> String {
+        format!("{}_id", Self::path())
+    }
+}
+
+/// The identifier of an entity as it appears in its url.
+///
+/// This Trait is automatically implemented with the `actix_restful_derive::actix_restful_info` attribute macro
+/// from the `id` field of the entity, or from the field named by its `id_field` parameter :
+/// `#[actix_restful_info(scope = "/v1", path = "user", id_field = "login")]`.
+/// It is used to build the `Location` header answered on create.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no `id` field to build the `Location` of the created entity",
+    label = "the entity answered by `save` must implement `Identifiable`",
+    note = "name the field holding its id with `#[actix_restful_info(.., id_field = \"..\")]`, or implement `actix_restful::Identifiable`"
+)]
+pub trait Identifiable {
+    fn resource_id(&self) -> String;
 }
 
 /// A macro to generate the http routes on the Actix app :
 ///
-/// ```
+/// ```no_run
+/// # use actix_restful::{gen_endpoint, Deletable, Findable, Listable, NewModel, NoQuery, RequestContext, RestfulPathInfo, UpdatableModel};
+/// # use anyhow::Result;
+/// # use async_trait::async_trait;
+/// # use serde::{Deserialize, Serialize};
+/// # struct AppState {}
+/// # #[derive(Serialize, actix_restful_derive::HttpFindListDelete)]
+/// # #[actix_restful_derive::actix_restful_info(scope = "/v1", path = "item")]
+/// # struct Item {
+/// #     id: i64,
+/// # }
+/// # #[async_trait]
+/// # impl Findable for Item {
+/// #     type Id = i64;
+/// #     type FindQuery = NoQuery;
+/// #     type State = AppState;
+/// #     async fn find(id: i64, _query: &NoQuery, _state: &AppState, _ctx: &RequestContext) -> Result<Box<Item>> {
+/// #         Ok(Box::new(Item { id }))
+/// #     }
+/// # }
+/// # #[async_trait]
+/// # impl Listable for Item {
+/// #     type ListQuery = NoQuery;
+/// #     type ListResult = Vec<Item>;
+/// #     type State = AppState;
+/// #     async fn list(_query: &NoQuery, _state: &AppState, _ctx: &RequestContext) -> Result<Vec<Item>> {
+/// #         Ok(vec![])
+/// #     }
+/// # }
+/// # #[async_trait]
+/// # impl Deletable for Item {
+/// #     type DeleteQuery = NoQuery;
+/// #     type DeleteResult = Item;
+/// #     async fn delete(self, _query: &NoQuery, _state: &AppState, _ctx: &RequestContext) -> Result<Item> {
+/// #         Ok(self)
+/// #     }
+/// # }
+/// # #[derive(Deserialize, actix_restful_derive::HttpCreate)]
+/// # struct NewItem {}
+/// # #[async_trait]
+/// # impl NewModel for NewItem {
+/// #     type Output = Item;
+/// #     type Query = NoQuery;
+/// #     type State = AppState;
+/// #     async fn save(self, _query: &NoQuery, _state: &AppState, _ctx: &RequestContext) -> Result<Item> {
+/// #         Ok(Item { id: 1 })
+/// #     }
+/// # }
+/// # #[derive(Serialize, Deserialize, actix_restful_derive::HttpUpdate)]
+/// # struct UpdatableItem {
+/// #     id: i64,
+/// # }
+/// # #[async_trait]
+/// # impl UpdatableModel for UpdatableItem {
+/// #     type Model = Item;
+/// #     type Output = UpdatableItem;
+/// #     type Query = NoQuery;
+/// #     type State = AppState;
+/// #     async fn update(self, _id: i64, _current: Item, _query: &NoQuery, _state: &AppState, _ctx: &RequestContext) -> Result<UpdatableItem> {
+/// #         Ok(self)
+/// #     }
+/// # }
 ///
 /// #[actix_web::main]
 /// async fn main() -> std::io::Result<()>{
@@ -309,36 +955,53 @@ pub trait RestfulPathInfo {
 ///
 /// - GET /v1/item/{id}
 /// - GET /v1/item
-/// - POST /v1/item
+/// - POST /v1/item, answering 201 Created with a `Location` header
 /// - PUT /v1/item/{id}
 /// - DELETE /v1/item/{id}
-
+///
+/// A fourth model implementing `HttpPatch` adds a sixth route :
+///
+/// ```ignore
+/// gen_endpoint!(Item, NewItem, UpdatableItem, PatchableItem)
+/// ```
+///
+/// - PATCH /v1/item/{id}
+///
+/// The operations to expose can also be picked one by one, the models of the operations left out
+/// don't need to derive their http trait :
+///
+/// ```ignore
+/// // read only
+/// gen_endpoint!(AuditLog => find, list)
+/// // no update nor delete
+/// gen_endpoint!(Item => find, list, create(NewItem), patch(PatchableItem))
+/// ```
+///
+/// The available operations are `find`, `list`, `delete`, `create(NewModel)`, `update(UpdatableModel)`
+/// and `patch(PatchableModel)`.
+///
+/// The macro expands to the `Resource` builder, use it directly to add guards or wrappers to the routes.
 #[macro_export]
 macro_rules! gen_endpoint {
-    ($model:ident, $new_model:ident, $updatable_model:ident) => {{
-        let path = $model::path();
-        use actix_web::web;
-        move |cfg: &mut web::ServiceConfig| {
-            cfg.route(
-                "/{path}/{id}".replace("{path}", &path).as_str(),
-                web::get().to($model::http_find),
-            )
-            .route(
-                "/{path}/{id}".replace("{path}", &path).as_str(),
-                web::delete().to($model::http_delete),
-            )
-            .route(
-                "/{path}/{id}".replace("{path}", &path).as_str(),
-                web::put().to($updatable_model::http_update),
-            )
-            .route(
-                "/{path}".replace("{path}", &path).as_str(),
-                web::get().to($model::http_list),
-            )
-            .route(
-                "/{path}".replace("{path}", &path).as_str(),
-                web::post().to($new_model::http_create),
-            );
-        }
+    ($model:ident => $($operation:ident $(($operation_model:ident))?),+ $(,)?) => {{
+        let resource = $crate::Resource::<$model>::new();
+        $(
+            let resource = $crate::gen_endpoint!(@route resource, $operation $(($operation_model))?);
+        )+
+        resource.configure()
     }};
+    ($model:ident, $new_model:ident, $updatable_model:ident) => {
+        $crate::gen_endpoint!($model => find, delete, update($updatable_model), list, create($new_model))
+    };
+    ($model:ident, $new_model:ident, $updatable_model:ident, $patchable_model:ident) => {
+        $crate::gen_endpoint!(
+            $model => find, delete, update($updatable_model), patch($patchable_model), list, create($new_model)
+        )
+    };
+    (@route $resource:ident, find) => { $resource.find() };
+    (@route $resource:ident, list) => { $resource.list() };
+    (@route $resource:ident, delete) => { $resource.delete() };
+    (@route $resource:ident, create($new_model:ident)) => { $resource.create::<$new_model>() };
+    (@route $resource:ident, update($updatable_model:ident)) => { $resource.update::<$updatable_model>() };
+    (@route $resource:ident, patch($patchable_model:ident)) => { $resource.patch::<$patchable_model>() };
 }
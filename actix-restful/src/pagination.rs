@@ -0,0 +1,246 @@
+use crate::{cursor_response, json_response, query::deserialize_params, CursorPage, Fields, RestError};
+use actix_web::http::{header, HeaderName, HeaderValue, StatusCode};
+use actix_web::{HttpRequest, HttpResponse};
+use serde::de::{Deserialize, Deserializer};
+use serde::Serialize;
+use serde_json::Value;
+use std::marker::PhantomData;
+
+/// The `offset` and `limit` parameters of a query string, `0` and `DEFAULT_LIMIT` when they are missing,
+/// the `limit` being capped to `MAX_LIMIT`.
+///
+/// A `list` answering a `Paginated` result is answered with the `Link` and `X-Total-Count` headers :
+///
+/// ```
+/// use actix_restful::{PageQuery, Paginated};
+///
+/// #[derive(serde::Deserialize)]
+/// struct ListQuery {
+///     #[serde(flatten)]
+///     page: PageQuery<10, 50>,
+/// }
+///
+/// let query = actix_web::web::Query::<ListQuery>::from_query("offset=20").unwrap();
+/// assert_eq!((query.page.offset(), query.page.limit()), (20, 10));
+/// let query = actix_web::web::Query::<ListQuery>::from_query("limit=500").unwrap();
+/// assert_eq!(query.page.limit(), 50);
+///
+/// let page = Paginated::slice((0..95).collect(), &query.page);
+/// assert_eq!((page.items.len(), page.total), (50, Some(95)));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PageQuery<const DEFAULT_LIMIT: usize = 20, const MAX_LIMIT: usize = 100> {
+    offset: usize,
+    limit: usize,
+}
+
+impl<const DEFAULT_LIMIT: usize, const MAX_LIMIT: usize> PageQuery<DEFAULT_LIMIT, MAX_LIMIT> {
+    pub fn new(offset: usize, limit: usize) -> Self {
+        PageQuery { offset, limit: limit.clamp(1, MAX_LIMIT.max(1)) }
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+}
+
+impl<const DEFAULT_LIMIT: usize, const MAX_LIMIT: usize> Default for PageQuery<DEFAULT_LIMIT, MAX_LIMIT> {
+    fn default() -> Self {
+        PageQuery::new(0, DEFAULT_LIMIT)
+    }
+}
+
+impl<'de, const DEFAULT_LIMIT: usize, const MAX_LIMIT: usize> Deserialize<'de> for PageQuery<DEFAULT_LIMIT, MAX_LIMIT> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (offset, limit) = deserialize_params(deserializer, (0, DEFAULT_LIMIT), |(offset, limit), key, value| {
+            let parameter = match key.as_str() {
+                "offset" => offset,
+                "limit" => limit,
+                _ => return Ok(()),
+            };
+            *parameter =
+                value.parse().map_err(|_| format!("invalid `{}` `{}`, expected a positive integer", key, value))?;
+            Ok(())
+        })?;
+        Ok(PageQuery::new(offset, limit))
+    }
+}
+
+/// A page of a list, the `ListResult` of a `list` answered with the `Link` header of the first, previous,
+/// next and last pages, and the `X-Total-Count` header when the `total` of the items is known.
+///
+/// The body is the envelope `{ "items": [..], "offset": 0, "limit": 20, "total": 95 }`.
+#[derive(Clone, Debug, Serialize)]
+pub struct Paginated<T> {
+    pub items: Vec<T>,
+    pub offset: usize,
+    pub limit: usize,
+    /// The number of items of the whole list, without a `last` link when it is unknown
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<usize>,
+}
+
+impl<T> Paginated<T> {
+    /// The page `page` of a list, whose `items` were fetched with its offset and limit
+    pub fn new<const DEFAULT_LIMIT: usize, const MAX_LIMIT: usize>(
+        items: Vec<T>,
+        page: &PageQuery<DEFAULT_LIMIT, MAX_LIMIT>,
+        total: Option<usize>,
+    ) -> Self {
+        Paginated { items, offset: page.offset(), limit: page.limit(), total }
+    }
+
+    /// The page `page` of all the items of a list, paginated in memory
+    pub fn slice<const DEFAULT_LIMIT: usize, const MAX_LIMIT: usize>(
+        items: Vec<T>,
+        page: &PageQuery<DEFAULT_LIMIT, MAX_LIMIT>,
+    ) -> Self {
+        let total = items.len();
+        let items = items.into_iter().skip(page.offset()).take(page.limit()).collect();
+        Paginated::new(items, page, Some(total))
+    }
+
+    /// The offsets of the first, previous, next and last pages, by relation
+    fn links(&self) -> Vec<(&'static str, usize)> {
+        let mut links = vec![("first", 0)];
+        if self.limit == 0 {
+            return links;
+        }
+        if self.offset > 0 {
+            links.push(("prev", self.offset.saturating_sub(self.limit)));
+        }
+        let next = self.offset + self.limit;
+        let has_next = match self.total {
+            Some(total) => next < total,
+            None => self.items.len() >= self.limit,
+        };
+        if has_next {
+            links.push(("next", next));
+        }
+        if let Some(total) = self.total {
+            links.push(("last", total.saturating_sub(1) / self.limit * self.limit));
+        }
+        links
+    }
+}
+
+/// Answers 200 OK with the page, its `Link` and `X-Total-Count` headers.
+///
+/// The links keep the other parameters of the query string, e.g. the filters, with the `offset` and `limit` of their page.
+pub fn paginated_response<T: Serialize>(req: &HttpRequest, page: &Paginated<T>) -> HttpResponse {
+    let mut res = json_response(req, StatusCode::OK, page);
+    if res.status() != StatusCode::OK {
+        return res;
+    }
+    let links: Vec<String> = page
+        .links()
+        .into_iter()
+        .map(|(rel, offset)| page_link(req, &format!("offset={}&limit={}", offset, page.limit), rel))
+        .collect();
+    if let Ok(value) = HeaderValue::from_str(&links.join(", ")) {
+        res.headers_mut().insert(header::LINK, value);
+    }
+    if let Some(total) = page.total {
+        res.headers_mut().insert(HeaderName::from_static("x-total-count"), HeaderValue::from(total));
+    }
+    res
+}
+
+/// The link `rel` to the page `params` of the list answering `req`, keeping the other parameters of its query string
+pub(crate) fn page_link(req: &HttpRequest, params: &str, rel: &str) -> String {
+    let mut query: Vec<&str> = req
+        .query_string()
+        .split('&')
+        .filter(|pair| {
+            let key = pair.split('=').next().unwrap_or_default();
+            !pair.is_empty() && !["offset", "limit", "cursor"].contains(&key)
+        })
+        .collect();
+    query.push(params);
+    format!("<{}?{}>; rel=\"{}\"", req.path(), query.join("&"), rel)
+}
+
+/// Answers the result of `list` in the generated handlers, projected on the requested `Fields`, with
+/// `paginated_response` for a `Paginated` result and `cursor_response` for a `CursorPage` : `PaginatedListing`
+/// applies to these results, `PlainListing` is found after an auto-ref for the others.
+#[doc(hidden)]
+pub struct Listing<'a, T>(pub &'a T);
+
+#[doc(hidden)]
+pub trait PaginatedListing {
+    fn list_response(&self, req: &HttpRequest, fields: &Fields) -> HttpResponse;
+}
+
+impl<T: Serialize> PaginatedListing for Listing<'_, Paginated<T>> {
+    fn list_response(&self, req: &HttpRequest, fields: &Fields) -> HttpResponse {
+        let page = self.0;
+        if fields.is_empty() {
+            return paginated_response(req, page);
+        }
+        match page.items.iter().map(|item| fields.project_value(item)).collect() {
+            Ok(items) => paginated_response(req, &Paginated { items, offset: page.offset, limit: page.limit, total: page.total }),
+            Err(err) => err.to_response(req),
+        }
+    }
+}
+
+impl<T: Serialize> PaginatedListing for Listing<'_, CursorPage<T>> {
+    fn list_response(&self, req: &HttpRequest, fields: &Fields) -> HttpResponse {
+        let page = self.0;
+        if fields.is_empty() {
+            return cursor_response(req, page);
+        }
+        match page.items.iter().map(|item| fields.project_value(item)).collect() {
+            Ok(items) => {
+                cursor_response(req, &CursorPage { items, limit: page.limit, next_cursor: page.next_cursor.clone() })
+            }
+            Err(err) => err.to_response(req),
+        }
+    }
+}
+
+#[doc(hidden)]
+pub trait PlainListing {
+    fn list_response(&self, req: &HttpRequest, fields: &Fields) -> HttpResponse;
+}
+
+/// The other results are projected when they are an array of items, and answered as they are otherwise
+impl<T: Serialize> PlainListing for &Listing<'_, T> {
+    fn list_response(&self, req: &HttpRequest, fields: &Fields) -> HttpResponse {
+        if fields.is_empty() {
+            return json_response(req, StatusCode::OK, self.0);
+        }
+        match serde_json::to_value(self.0) {
+            Ok(items @ Value::Array(_)) => json_response(req, StatusCode::OK, &fields.project(items)),
+            Ok(value) => json_response(req, StatusCode::OK, &value),
+            Err(err) => RestError::Internal(err.into()).to_response(req),
+        }
+    }
+}
+
+/// Whether the result of `list` is a `CursorPage`, found like `PaginatedListing` and `PlainListing` :
+/// `Resource::configure` checks that the secret signing the cursors is set.
+#[doc(hidden)]
+pub struct ListingKind<T>(pub PhantomData<T>);
+
+#[doc(hidden)]
+pub trait CursorListingKind {
+    fn uses_cursors(&self) -> bool {
+        true
+    }
+}
+
+impl<T> CursorListingKind for ListingKind<CursorPage<T>> {}
+
+#[doc(hidden)]
+pub trait PlainListingKind {
+    fn uses_cursors(&self) -> bool {
+        false
+    }
+}
+
+impl<T> PlainListingKind for &ListingKind<T> {}
@@ -0,0 +1,228 @@
+use crate::{check_body_id, parse_json, RestError};
+use actix_web::{http::header, HttpRequest};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+pub const MERGE_PATCH_JSON: &str = "application/merge-patch+json";
+
+/// Applies an RFC 7396 JSON Merge Patch on `target`
+///
+/// ```
+/// use serde_json::json;
+///
+/// let mut item = json!({ "id": 1, "content": "test", "tags": ["a"] });
+/// actix_restful::merge_patch(&mut item, &json!({ "content": "patched", "tags": null }));
+/// assert_eq!(item, json!({ "id": 1, "content": "patched" }));
+/// ```
+pub fn merge_patch(target: &mut Value, patch: &Value) {
+    match patch {
+        Value::Object(patch) => {
+            if !target.is_object() {
+                *target = Value::Object(serde_json::Map::new());
+            }
+            if let Value::Object(target) = target {
+                for (key, value) in patch {
+                    if value.is_null() {
+                        target.remove(key);
+                    } else {
+                        merge_patch(target.entry(key.clone()).or_insert(Value::Null), value);
+                    }
+                }
+            }
+        }
+        _ => *target = patch.clone(),
+    }
+}
+
+fn content_type(req: &HttpRequest) -> Option<&str> {
+    req.headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.split(';').next().unwrap_or("").trim())
+}
+
+/// Applies the patch document `body` on top of the serialized `entity`, and deserializes the result.
+///
+/// The document is a JSON Patch (RFC 6902) when the request content type is `application/json-patch+json`,
+/// a JSON Merge Patch (RFC 7396) when it is `application/merge-patch+json` or `application/json`.
+/// A patch changing the `id` of the entity away from `path_id` is answered 409 Conflict, see `check_body_id`.
+///
+/// Used by the handler generated with the `actix_restful_derive::HttpPatch` derive macro.
+pub fn apply_patch<E, T, I>(req: &HttpRequest, entity: &E, body: &[u8], path_id: &I) -> Result<T, RestError>
+where
+    E: Serialize + ?Sized,
+    T: DeserializeOwned,
+    I: DeserializeOwned + PartialEq,
+{
+    let mut target = serde_json::to_value(entity).map_err(|err| RestError::Internal(err.into()))?;
+    match content_type(req) {
+        Some(JSON_PATCH_JSON) => {
+            let operations: Vec<PatchOperation> = parse_json(body)?;
+            json_patch(&mut target, &operations)?;
+        }
+        Some(MERGE_PATCH_JSON) | Some("application/json") => {
+            let patch: Value = parse_json(body)?;
+            merge_patch(&mut target, &patch);
+        }
+        _ => {
+            return Err(RestError::UnsupportedMediaType(format!(
+                "expected a {} or {} document",
+                MERGE_PATCH_JSON, JSON_PATCH_JSON
+            )))
+        }
+    }
+    check_body_id(&target, path_id)?;
+    serde_json::from_value(target).map_err(|err| RestError::Validation(err.to_string()))
+}
+
+pub const JSON_PATCH_JSON: &str = "application/json-patch+json";
+
+/// One operation of an RFC 6902 JSON Patch document
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+    Move { from: String, path: String },
+    Copy { from: String, path: String },
+    Test { path: String, value: Value },
+}
+
+fn parse_pointer(pointer: &str) -> Result<Vec<String>, RestError> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+    if !pointer.starts_with('/') {
+        return Err(RestError::BadRequest(format!("invalid json pointer \"{}\"", pointer)));
+    }
+    Ok(pointer[1..]
+        .split('/')
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect())
+}
+
+fn unprocessable(pointer: &str) -> RestError {
+    RestError::Validation(format!("path \"{}\" does not exist", pointer))
+}
+
+fn array_index(token: &str, len: usize, pointer: &str) -> Result<usize, RestError> {
+    match token.parse::<usize>() {
+        Ok(index) if index < len && (token == "0" || !token.starts_with('0')) => Ok(index),
+        _ => Err(unprocessable(pointer)),
+    }
+}
+
+/// Splits `pointer` into the value holding the last token, and the last token
+fn parent<'a>(doc: &'a mut Value, pointer: &str) -> Result<(&'a mut Value, String), RestError> {
+    let mut tokens = parse_pointer(pointer)?;
+    let last = tokens.pop().ok_or_else(|| unprocessable(pointer))?;
+    let mut current = doc;
+    for token in tokens {
+        current = match current {
+            Value::Object(map) => map.get_mut(&token).ok_or_else(|| unprocessable(pointer))?,
+            Value::Array(arr) => {
+                let index = array_index(&token, arr.len(), pointer)?;
+                &mut arr[index]
+            }
+            _ => return Err(unprocessable(pointer)),
+        };
+    }
+    Ok((current, last))
+}
+
+fn get<'a>(doc: &'a Value, pointer: &str) -> Result<&'a Value, RestError> {
+    parse_pointer(pointer)?;
+    doc.pointer(pointer).ok_or_else(|| unprocessable(pointer))
+}
+
+fn add(doc: &mut Value, pointer: &str, value: Value) -> Result<(), RestError> {
+    if pointer.is_empty() {
+        *doc = value;
+        return Ok(());
+    }
+    let (parent, last) = parent(doc, pointer)?;
+    match parent {
+        Value::Object(map) => {
+            map.insert(last, value);
+        }
+        Value::Array(arr) => {
+            if last == "-" {
+                arr.push(value);
+            } else {
+                let index = array_index(&last, arr.len() + 1, pointer)?;
+                arr.insert(index, value);
+            }
+        }
+        _ => return Err(unprocessable(pointer)),
+    }
+    Ok(())
+}
+
+fn remove(doc: &mut Value, pointer: &str) -> Result<Value, RestError> {
+    let (parent, last) = parent(doc, pointer)?;
+    match parent {
+        Value::Object(map) => map.remove(&last).ok_or_else(|| unprocessable(pointer)),
+        Value::Array(arr) => {
+            let index = array_index(&last, arr.len(), pointer)?;
+            Ok(arr.remove(index))
+        }
+        _ => Err(unprocessable(pointer)),
+    }
+}
+
+/// Applies the operations of an RFC 6902 JSON Patch on `target`.
+///
+/// The operations are applied atomically : `target` is left untouched when one of them fails.
+/// A failing `test` operation is a `RestError::Conflict`, an operation on a path that does not
+/// exist is a `RestError::Validation`.
+///
+/// ```
+/// use actix_restful::{json_patch, PatchOperation};
+/// use serde_json::json;
+///
+/// let mut item = json!({ "id": 1, "content": "test" });
+/// let ops: Vec<PatchOperation> = serde_json::from_value(json!([
+///     { "op": "test", "path": "/content", "value": "test" },
+///     { "op": "replace", "path": "/content", "value": "patched" },
+/// ])).unwrap();
+/// json_patch(&mut item, &ops).unwrap();
+/// assert_eq!(item, json!({ "id": 1, "content": "patched" }));
+/// ```
+pub fn json_patch(target: &mut Value, operations: &[PatchOperation]) -> Result<(), RestError> {
+    let mut doc = target.clone();
+    for operation in operations {
+        match operation {
+            PatchOperation::Add { path, value } => add(&mut doc, path, value.clone())?,
+            PatchOperation::Remove { path } => {
+                remove(&mut doc, path)?;
+            }
+            PatchOperation::Replace { path, value } if path.is_empty() => doc = value.clone(),
+            PatchOperation::Replace { path, value } => {
+                remove(&mut doc, path)?;
+                add(&mut doc, path, value.clone())?;
+            }
+            PatchOperation::Move { from, path } => {
+                if path.starts_with(&format!("{}/", from)) {
+                    return Err(RestError::Validation(format!(
+                        "can not move \"{}\" into one of its children",
+                        from
+                    )));
+                }
+                let value = remove(&mut doc, from)?;
+                add(&mut doc, path, value)?;
+            }
+            PatchOperation::Copy { from, path } => {
+                let value = get(&doc, from)?.clone();
+                add(&mut doc, path, value)?;
+            }
+            PatchOperation::Test { path, value } => {
+                if get(&doc, path)? != value {
+                    return Err(RestError::Conflict(format!("test failed on path \"{}\"", path)));
+                }
+            }
+        }
+    }
+    *target = doc;
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use crate::{RequestContext, RestError, RestfulPathInfo};
+use actix_router::PathDeserializer;
+use actix_web::dev::Payload;
+use actix_web::{FromRequest, HttpRequest};
+use async_trait::async_trait;
+use serde::de::{self, DeserializeOwned, DeserializeSeed, IgnoredAny, MapAccess, Visitor};
+use std::fmt;
+use std::future::{ready, Ready};
+use std::marker::PhantomData;
+
+/// A path parameter name, the path deserializer only hands its keys as identifiers
+struct ParamName(String);
+
+impl<'de> de::Deserialize<'de> for ParamName {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct NameVisitor;
+        impl<'de> Visitor<'de> for NameVisitor {
+            type Value = ParamName;
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a path parameter name")
+            }
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<ParamName, E> {
+                Ok(ParamName(v.to_string()))
+            }
+        }
+        deserializer.deserialize_identifier(NameVisitor)
+    }
+}
+
+struct Param<'a, T> {
+    name: &'a str,
+    value: PhantomData<T>,
+}
+
+impl<'de, 'a, T: DeserializeOwned> DeserializeSeed<'de> for Param<'a, T> {
+    type Value = T;
+
+    fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<T, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, 'a, T: DeserializeOwned> Visitor<'de> for Param<'a, T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a path with a {} parameter", self.name)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<T, A::Error> {
+        let mut value = None;
+        while let Some(ParamName(key)) = map.next_key()? {
+            if key == self.name {
+                value = Some(map.next_value::<T>()?);
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        value.ok_or_else(|| de::Error::missing_field("path parameter"))
+    }
+}
+
+/// Deserializes the path parameter `name` of the request
+pub fn path_param<T: DeserializeOwned>(req: &HttpRequest, name: &str) -> Result<T, RestError> {
+    let seed = Param {
+        name,
+        value: PhantomData,
+    };
+    seed.deserialize(PathDeserializer::new(req.match_info()))
+        .map_err(|err: de::value::Error| {
+            RestError::BadRequest(format!("Invalid path parameter {}: {}", name, err))
+        })
+}
+
+/// The names of the parameters of a path pattern, `{tenant}/{code:[A-Z0-9]+}` holds `tenant` and `code`
+fn param_names(pattern: &str) -> Vec<&str> {
+    pattern
+        .split('{')
+        .skip(1)
+        .filter_map(|param| param.split('}').next())
+        .map(|param| param.split(':').next().unwrap_or(param))
+        .collect()
+}
+
+/// Deserializes the id of a model from the request path, following the `RestfulPathInfo::id_pattern` of the model.
+///
+/// A pattern with a single parameter, like `{id}` or `{slug}`, is read as a plain value : an integer,
+/// a `String`, a `Uuid`, ...
+/// A pattern with several parameters, like `{tenant}/{code}`, is read as a struct whose fields are named
+/// after the parameters :
+///
+/// ```ignore
+/// #[derive(Clone, Deserialize, Serialize)]
+/// pub struct ItemKey {
+///     tenant: String,
+///     code: String,
+/// }
+/// ```
+///
+/// An id which does not parse is answered with a 400 Bad Request.
+pub fn path_id<T: DeserializeOwned>(req: &HttpRequest, id_pattern: &str) -> Result<T, RestError> {
+    match param_names(id_pattern).as_slice() {
+        [name] => path_param(req, name),
+        _ => T::deserialize(PathDeserializer::new(req.match_info()))
+            .map_err(|err: de::value::Error| RestError::BadRequest(format!("Invalid path: {}", err))),
+    }
+}
+
+/// Extracts the id of the model `M` from the request path, see `path_id`.
+///
+/// The generated handlers take it as the path of the entity.
+pub struct IdPath<M, I> {
+    pub id: I,
+    model: PhantomData<fn() -> M>,
+}
+
+impl<M, I> IdPath<M, I> {
+    pub fn into_inner(self) -> I {
+        self.id
+    }
+}
+
+impl<M: RestfulPathInfo, I: DeserializeOwned> FromRequest for IdPath<M, I> {
+    type Config = ();
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, actix_web::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(
+            path_id(req, &M::id_pattern())
+                .map(|id| IdPath {
+                    id,
+                    model: PhantomData,
+                })
+                .map_err(|err| err.into_error(req)),
+        )
+    }
+}
+
+/// Checks the parent of a nested resource exists.
+///
+/// This Trait is automatically implemented with the `actix_restful_derive::HttpFindListDelete` derive macro.
+/// `lookup` reads the id of the model in the path parameter named by `RestfulPathInfo::parent_id_param`,
+/// and calls `find` with it and the `RequestContext` of the handler.
+#[async_trait(?Send)]
+pub trait ParentLookup<AppState> {
+    type Id: Clone;
+    async fn lookup(req: &HttpRequest, state: &AppState, ctx: &RequestContext) -> Result<Self::Id, RestError>;
+}
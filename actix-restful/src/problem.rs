@@ -0,0 +1,110 @@
+use crate::{FieldError, RestError, RestfulConfig};
+use actix_web::error::{InternalError, JsonPayloadError};
+use actix_web::{http::StatusCode, web, HttpRequest, HttpResponse};
+use serde::Serialize;
+
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// An RFC 7807 problem document, the body of every error answered by the generated handlers
+#[derive(Debug, Serialize)]
+pub struct Problem {
+    #[serde(rename = "type")]
+    pub type_uri: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// The invalid fields of a payload, answered with `RestError::Invalid` and `RestError::InvalidBody`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Vec<FieldError>>,
+}
+
+impl Problem {
+    /// Builds the problem document of an error, without `instance`.
+    ///
+    /// The detail of an `Internal` error is not disclosed, it is logged by `RestError::to_response`.
+    pub fn new(err: &RestError, config: &RestfulConfig) -> Self {
+        let status = err.status();
+        Problem {
+            type_uri: config.problem_type_of(err),
+            title: status.canonical_reason().unwrap_or("Unknown").to_string(),
+            status: status.as_u16(),
+            detail: match err {
+                RestError::Internal(_) => String::from("internal server error"),
+                err => err.to_string(),
+            },
+            instance: None,
+            errors: match err {
+                RestError::Invalid(errors) => Some(errors.errors().to_vec()),
+                RestError::InvalidBody(err) => Some(vec![err.as_ref().clone()]),
+                _ => None,
+            },
+        }
+    }
+
+    /// Builds the problem document of an error raised while answering `req`
+    pub fn from_req(err: &RestError, req: &HttpRequest) -> Self {
+        let mut problem = Problem::new(err, &RestfulConfig::from_req(req));
+        problem.instance = Some(req.path().to_string());
+        problem
+    }
+
+    pub fn into_response(self) -> HttpResponse {
+        let body = serde_json::to_string(&self).unwrap_or_default();
+        HttpResponse::build(StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR))
+            .content_type(PROBLEM_JSON)
+            .body(body)
+    }
+}
+
+fn extractor_error<E>(cause: E, err: RestError, req: &HttpRequest) -> actix_web::Error
+where
+    E: std::fmt::Debug + std::fmt::Display + 'static,
+{
+    InternalError::from_response(cause, err.to_response(req)).into()
+}
+
+/// Makes the path, query and `web::Json` extractors of the handlers of a scope or an app answer problem documents,
+/// registered once with `App::configure` or `Scope::configure`.
+///
+/// The routes registered by `Resource` and `gen_endpoint!` carry these settings themselves, see `ExtractorConfigs`.
+pub fn configure_extractors(cfg: &mut web::ServiceConfig) {
+    let configs = ExtractorConfigs::new();
+    cfg.app_data(configs.path).app_data(configs.query).app_data(configs.json);
+}
+
+/// The settings of the path, query and `web::Json` extractors answering problem documents
+#[derive(Clone)]
+pub(crate) struct ExtractorConfigs {
+    path: web::PathConfig,
+    query: web::QueryConfig,
+    json: web::JsonConfig,
+}
+
+impl ExtractorConfigs {
+    pub(crate) fn new() -> Self {
+        ExtractorConfigs {
+            path: web::PathConfig::default().error_handler(|err, req| {
+                let rest_err = RestError::BadRequest(err.to_string());
+                extractor_error(err, rest_err, req)
+            }),
+            query: web::QueryConfig::default().error_handler(|err, req| {
+                let rest_err = RestError::BadRequest(err.to_string());
+                extractor_error(err, rest_err, req)
+            }),
+            json: web::JsonConfig::default().error_handler(|err, req| {
+                let rest_err = match err {
+                    JsonPayloadError::Overflow => RestError::PayloadTooLarge(err.to_string()),
+                    _ => RestError::BadRequest(err.to_string()),
+                };
+                extractor_error(err, rest_err, req)
+            }),
+        }
+    }
+
+    /// Sets these settings on a route, leaving those of its scope to the other handlers
+    pub(crate) fn apply(&self, resource: actix_web::Resource) -> actix_web::Resource {
+        resource.app_data(self.path.clone()).app_data(self.query.clone()).app_data(self.json.clone())
+    }
+}
@@ -0,0 +1,44 @@
+use serde::de::{Deserializer, Error as _, MapAccess, Visitor};
+use serde::Deserialize;
+use std::fmt;
+
+/// The query of an operation without parameters, the default query type of the derives.
+///
+/// Any query string parameter is ignored.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct NoQuery {}
+
+/// Deserializes the parameters of a query string into `value`, handing each key and value to `param`,
+/// the error of `param` failing the deserialization.
+///
+/// The query types read their parameters with it, ignoring the others, see the crate documentation.
+pub(crate) fn deserialize_params<'de, D, T, F>(deserializer: D, value: T, param: F) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    F: FnMut(&mut T, String, String) -> Result<(), String>,
+{
+    deserializer.deserialize_map(ParamsVisitor { value, param })
+}
+
+struct ParamsVisitor<T, F> {
+    value: T,
+    param: F,
+}
+
+impl<'de, T, F> Visitor<'de> for ParamsVisitor<T, F>
+where
+    F: FnMut(&mut T, String, String) -> Result<(), String>,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the parameters of a query string")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(mut self, mut map: A) -> Result<T, A::Error> {
+        while let Some((key, value)) = map.next_entry::<String, String>()? {
+            (self.param)(&mut self.value, key, value).map_err(A::Error::custom)?;
+        }
+        Ok(self.value)
+    }
+}
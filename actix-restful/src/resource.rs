@@ -0,0 +1,307 @@
+use crate::problem::ExtractorConfigs;
+use crate::{cursor, RestError, RestfulPathInfo};
+use actix_web::dev::HttpServiceFactory;
+use actix_web::http::{header, Method};
+use actix_web::{guard, web, HttpRequest, HttpResponse, Route};
+use std::marker::PhantomData;
+
+/// The route of `GET /{path}/{id}`, implemented with the `actix_restful_derive::HttpFind` derive macro
+pub trait FindRoute {
+    fn find_route() -> Route;
+}
+
+/// The route of `GET /{path}`, implemented with the `actix_restful_derive::HttpList` derive macro
+pub trait ListRoute {
+    fn list_route() -> Route;
+
+    /// Whether the list answers a `CursorPage`, whose cursors are signed with the secret of `RestfulConfig::cursor_key`
+    fn uses_cursors() -> bool {
+        false
+    }
+}
+
+/// The route of `DELETE /{path}/{id}`, implemented with the `actix_restful_derive::HttpDelete` derive macro
+pub trait DeleteRoute {
+    fn delete_route() -> Route;
+}
+
+/// The route of `POST /{path}`, implemented with the `actix_restful_derive::HttpCreate` derive macro
+pub trait CreateRoute {
+    fn create_route() -> Route;
+}
+
+/// The route of `PUT /{path}/{id}`, implemented with the `actix_restful_derive::HttpUpdate` derive macro
+pub trait UpdateRoute {
+    fn update_route() -> Route;
+}
+
+/// The route of `PATCH /{path}/{id}`, implemented with the `actix_restful_derive::HttpPatch` derive macro
+pub trait PatchRoute {
+    fn patch_route() -> Route;
+}
+
+/// The routes of a model, implemented with the `actix_restful_derive::Restful` derive macro :
+/// `web::scope(Item::scope()).configure(<Item as RestfulRoutes>::configure)`, or `Item::configure`
+/// with the trait in scope.
+pub trait RestfulRoutes {
+    /// Registers the routes of the model, to hand to `Scope::configure`
+    fn configure(cfg: &mut web::ServiceConfig);
+}
+
+/// Registers a route, given the resource of its path
+type Register = Box<dyn FnOnce(&mut web::ServiceConfig, actix_web::Resource)>;
+
+/// A route of the model, registered on the path of the collection or on the path of an item
+struct Registration {
+    with_id: bool,
+    method: Method,
+    register: Register,
+}
+
+/// A builder registering the routes of a model, the programmatic counterpart of `gen_endpoint!` :
+///
+/// ```ignore
+/// actix_web::App::new()
+///     .service(
+///         actix_web::web::scope(Item::scope()).configure(
+///             Resource::<Item>::new()
+///                 .find()
+///                 .list()
+///                 .create::<NewItem>()
+///                 .update_with::<UpdatableItem, _, _>(|resource| {
+///                     resource.guard(guard::Header("x-role", "admin")).wrap(Logger::default())
+///                 })
+///                 .delete()
+///                 .configure(),
+///         ),
+///     )
+/// ```
+///
+/// The `{id}` of the routes is the `RestfulPathInfo::id_pattern` of the model, and the size of their bodies
+/// is limited by its `RestfulPathInfo::body_limit`, or by `body_limit`. Their path, query and json extractors answer
+/// problem documents, and the models declared without state get `()`, set on these routes only : the other handlers
+/// of the scope keep their settings, see `configure_extractors`.
+///
+/// Each route is registered as its own `actix_web::Resource`, guarded by the method of the route.
+/// The `*_with` variants hand that resource to a closure, to add guards, wrappers or data to this route only.
+///
+/// The other methods are answered with a 405 Method Not Allowed listing the registered ones in its `Allow` header,
+/// e.g. `DELETE /{path}/{id}` on a model registered without `delete`. A request of a registered method rejected
+/// by the guards added to its route is answered 404 Not Found, as actix-web does.
+pub struct Resource<T> {
+    registrations: Vec<Registration>,
+    body_limit: Option<usize>,
+    uses_cursors: bool,
+    model: PhantomData<T>,
+}
+
+impl<T> Default for Resource<T> {
+    fn default() -> Self {
+        Resource {
+            registrations: Vec::new(),
+            body_limit: None,
+            uses_cursors: false,
+            model: PhantomData,
+        }
+    }
+}
+
+impl<T: RestfulPathInfo> Resource<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn register<F, R>(mut self, with_id: bool, method: Method, route: Route, f: F) -> Self
+    where
+        F: FnOnce(actix_web::Resource) -> R + 'static,
+        R: HttpServiceFactory + 'static,
+    {
+        self.registrations.push(Registration {
+            with_id,
+            method,
+            register: Box::new(move |cfg, resource| {
+                cfg.service(f(resource.route(route)));
+            }),
+        });
+        self
+    }
+
+    /// Sets the limit of the size of the bodies, overriding `RestfulPathInfo::body_limit`
+    pub fn body_limit(mut self, limit: usize) -> Self {
+        self.body_limit = Some(limit);
+        self
+    }
+
+    /// Registers `GET /{path}/{id}`
+    pub fn find(self) -> Self
+    where
+        T: FindRoute,
+    {
+        self.find_with(|resource| resource)
+    }
+
+    /// Registers `GET /{path}/{id}`, customized by `f`
+    pub fn find_with<F, R>(self, f: F) -> Self
+    where
+        T: FindRoute,
+        F: FnOnce(actix_web::Resource) -> R + 'static,
+        R: HttpServiceFactory + 'static,
+    {
+        self.register(true, Method::GET, T::find_route(), f)
+    }
+
+    /// Registers `GET /{path}`
+    pub fn list(self) -> Self
+    where
+        T: ListRoute,
+    {
+        self.list_with(|resource| resource)
+    }
+
+    /// Registers `GET /{path}`, customized by `f`
+    pub fn list_with<F, R>(mut self, f: F) -> Self
+    where
+        T: ListRoute,
+        F: FnOnce(actix_web::Resource) -> R + 'static,
+        R: HttpServiceFactory + 'static,
+    {
+        self.uses_cursors = T::uses_cursors();
+        self.register(false, Method::GET, T::list_route(), f)
+    }
+
+    /// Registers `DELETE /{path}/{id}`
+    pub fn delete(self) -> Self
+    where
+        T: DeleteRoute,
+    {
+        self.delete_with(|resource| resource)
+    }
+
+    /// Registers `DELETE /{path}/{id}`, customized by `f`
+    pub fn delete_with<F, R>(self, f: F) -> Self
+    where
+        T: DeleteRoute,
+        F: FnOnce(actix_web::Resource) -> R + 'static,
+        R: HttpServiceFactory + 'static,
+    {
+        self.register(true, Method::DELETE, T::delete_route(), f)
+    }
+
+    /// Registers `POST /{path}`, handled by the `HttpCreate` implementation of `N`
+    pub fn create<N: CreateRoute>(self) -> Self {
+        self.create_with::<N, _, _>(|resource| resource)
+    }
+
+    /// Registers `POST /{path}`, customized by `f`
+    pub fn create_with<N, F, R>(self, f: F) -> Self
+    where
+        N: CreateRoute,
+        F: FnOnce(actix_web::Resource) -> R + 'static,
+        R: HttpServiceFactory + 'static,
+    {
+        self.register(false, Method::POST, N::create_route(), f)
+    }
+
+    /// Registers `PUT /{path}/{id}`, handled by the `HttpUpdate` implementation of `U`
+    pub fn update<U: UpdateRoute>(self) -> Self {
+        self.update_with::<U, _, _>(|resource| resource)
+    }
+
+    /// Registers `PUT /{path}/{id}`, customized by `f`
+    pub fn update_with<U, F, R>(self, f: F) -> Self
+    where
+        U: UpdateRoute,
+        F: FnOnce(actix_web::Resource) -> R + 'static,
+        R: HttpServiceFactory + 'static,
+    {
+        self.register(true, Method::PUT, U::update_route(), f)
+    }
+
+    /// Registers `PATCH /{path}/{id}`, handled by the `HttpPatch` implementation of `P`
+    pub fn patch<P: PatchRoute>(self) -> Self {
+        self.patch_with::<P, _, _>(|resource| resource)
+    }
+
+    /// Registers `PATCH /{path}/{id}`, customized by `f`
+    pub fn patch_with<P, F, R>(self, f: F) -> Self
+    where
+        P: PatchRoute,
+        F: FnOnce(actix_web::Resource) -> R + 'static,
+        R: HttpServiceFactory + 'static,
+    {
+        self.register(true, Method::PATCH, P::patch_route(), f)
+    }
+
+    /// The configuration to hand to `Scope::configure` or `App::configure`.
+    ///
+    /// It panics when the list answers a `CursorPage` and no `RestfulConfig::cursor_key` was set beforehand,
+    /// the cursors of its pages could not be signed.
+    pub fn configure(self) -> impl FnOnce(&mut web::ServiceConfig) {
+        move |cfg: &mut web::ServiceConfig| {
+            let collection_path = format!("/{}", T::route_path());
+            if self.uses_cursors {
+                cursor::check_cursor_key(&collection_path);
+            }
+            let item_path = format!("{}/{}", collection_path, T::id_pattern());
+            let extractors = ExtractorConfigs::new();
+            // the state of the models declared without one, set on their routes only
+            let no_state = web::Data::new(());
+            let collection_allow = allowed_methods(&self.registrations, false);
+            let item_allow = allowed_methods(&self.registrations, true);
+            let body_limit = self.body_limit.unwrap_or_else(T::body_limit);
+            for registration in self.registrations {
+                let pattern = if registration.with_id { &item_path } else { &collection_path };
+                let resource = web::resource(pattern)
+                    .guard(guard::Method(registration.method))
+                    .app_data(web::PayloadConfig::new(body_limit))
+                    .app_data(no_state.clone());
+                let resource = extractors.apply(resource);
+                (registration.register)(cfg, resource);
+            }
+            // registered last, answers the other methods : the requests of a registered method rejected by the
+            // guards of its route fall through to 404 Not Found
+            for (pattern, methods) in [(collection_path, collection_allow), (item_path, item_allow)] {
+                if let Some(guard) = other_methods(&methods) {
+                    let allow = methods.iter().map(Method::as_str).collect::<Vec<_>>().join(", ");
+                    cfg.service(web::resource(pattern).guard(guard).to(move |req: HttpRequest| {
+                        std::future::ready(method_not_allowed(&req, &allow))
+                    }));
+                }
+            }
+        }
+    }
+}
+
+/// The methods registered on the collection, or on an item when `with_id` is set, listed in the `Allow` header
+fn allowed_methods(registrations: &[Registration], with_id: bool) -> Vec<Method> {
+    let mut methods: Vec<Method> = Vec::new();
+    for registration in registrations.iter().filter(|r| r.with_id == with_id) {
+        if !methods.contains(&registration.method) {
+            methods.push(registration.method.clone());
+        }
+    }
+    methods
+}
+
+/// The guard of the requests whose method is none of `methods`, `None` when no method is registered
+fn other_methods(methods: &[Method]) -> Option<impl guard::Guard> {
+    let (first, others) = methods.split_first()?;
+    let any = others.iter().fold(guard::Any(guard::Method(first.clone())), |any, method| {
+        any.or(guard::Method(method.clone()))
+    });
+    Some(guard::Not(any))
+}
+
+fn method_not_allowed(req: &HttpRequest, allow: &str) -> HttpResponse {
+    let err = RestError::MethodNotAllowed(format!(
+        "{} is not allowed on {}, allowed methods : {}",
+        req.method(),
+        req.path(),
+        allow
+    ));
+    let mut res = err.to_response(req);
+    if let Ok(value) = header::HeaderValue::from_str(allow) {
+        res.headers_mut().insert(header::ALLOW, value);
+    }
+    res
+}
@@ -0,0 +1,72 @@
+use crate::{Identifiable, RestError, RestfulConfig, RestfulPathInfo};
+use actix_web::{http::header, http::StatusCode, HttpRequest, HttpResponse};
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+use serde::Serialize;
+
+/// The characters escaped in a segment of a path, those which are not allowed there or delimit it
+const SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+/// The characters escaped in a path parameter of the request, whose `%2F` and `%2B` are left encoded by the router
+const PARAM: &AsciiSet = &SEGMENT.remove(b'%');
+
+/// Serializes `value` as the `application/json` body of a response to `req`.
+///
+/// The body is pretty printed when `RestfulConfig::pretty` is set.
+pub fn json_response<T: Serialize + ?Sized>(
+    req: &HttpRequest,
+    status: StatusCode,
+    value: &T,
+) -> HttpResponse {
+    let body = if RestfulConfig::from_req(req).is_pretty() {
+        serde_json::to_string_pretty(value)
+    } else {
+        serde_json::to_string(value)
+    };
+    match body {
+        Ok(body) => HttpResponse::build(status)
+            .content_type("application/json")
+            .body(body),
+        Err(err) => RestError::Internal(err.into()).to_response(req),
+    }
+}
+
+/// Answers 201 Created with the created entity, and its url in the `Location` header.
+///
+/// The path parameters of the parents of a nested model are taken from the request. The id and these parameters
+/// are percent-encoded, an id holding a `/` or a `?` staying a single segment of the url.
+/// The id of a model whose `RestfulPathInfo::id_pattern` spans several segments, like `{tenant}/{code}`, is split
+/// on its first `/` into as many segments.
+pub fn created_response<T>(req: &HttpRequest, entity: &T) -> HttpResponse
+where
+    T: Serialize + RestfulPathInfo + Identifiable,
+{
+    let segments = T::id_pattern().split('/').count();
+    let id = entity.resource_id();
+    let id = id
+        .splitn(segments, '/')
+        .map(|segment| utf8_percent_encode(segment, SEGMENT).to_string())
+        .collect::<Vec<_>>()
+        .join("/");
+    let mut location = format!("{}/{}/{}", T::scope().trim_end_matches('/'), T::route_path(), id);
+    for (name, value) in req.match_info().iter() {
+        location = location.replace(&format!("{{{}}}", name), &utf8_percent_encode(value, PARAM).to_string());
+    }
+    let mut res = json_response(req, StatusCode::CREATED, entity);
+    if res.status() == StatusCode::CREATED {
+        if let Ok(value) = header::HeaderValue::from_str(&location) {
+            res.headers_mut().insert(header::LOCATION, value);
+        }
+    }
+    res
+}
@@ -0,0 +1,261 @@
+use crate::query::deserialize_params;
+use serde::de::{Deserialize, Deserializer};
+use serde::Serialize;
+use serde_json::{Number, Value};
+use std::cmp::Ordering;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// The models whose lists can be sorted with a `SortQuery`, by the fields they allow.
+///
+/// It is implemented by the `sort` parameter of `actix_restful_info` or `restful`, the fields being named as serde
+/// serializes them :
+///
+/// ```ignore
+/// #[actix_restful_info(scope = "/v1", path = "issue", sort = "created_at, name")]
+/// struct Issue {
+///     id: i64,
+///     name: String,
+///     created_at: DateTime<Utc>,
+/// }
+/// ```
+pub trait Sortable {
+    fn sort_fields() -> &'static [&'static str];
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+impl SortDirection {
+    /// The keyword of the direction in an `ORDER BY` clause
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            SortDirection::Asc => "ASC",
+            SortDirection::Desc => "DESC",
+        }
+    }
+}
+
+/// A field of the `sort` parameter, descending when it is prefixed with `-`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SortField {
+    pub field: &'static str,
+    pub direction: SortDirection,
+}
+
+/// The `sort` parameter of a query string, on the fields allowed by the `Sortable` model `M`.
+///
+/// `?sort=-created_at,name` is parsed into the fields `created_at` descending then `name` ascending,
+/// for the `list` implementations to translate to an `ORDER BY` clause, or to apply in memory with `sort`.
+/// A field which is not allowed, or given twice, is answered `400 Bad Request`.
+///
+/// E.g. flattened in the list query of a model :
+///
+/// ```
+/// use actix_restful::{SortDirection, SortField, SortQuery, Sortable};
+///
+/// #[derive(serde::Serialize)]
+/// struct Issue {
+///     name: &'static str,
+///     priority: u8,
+/// }
+///
+/// impl Sortable for Issue {
+///     fn sort_fields() -> &'static [&'static str] {
+///         &["name", "priority"]
+///     }
+/// }
+///
+/// #[derive(serde::Deserialize)]
+/// struct ListQuery {
+///     limit: usize,
+///     #[serde(flatten)]
+///     sort: SortQuery<Issue>,
+/// }
+///
+/// let query = actix_web::web::Query::<ListQuery>::from_query("limit=10&sort=-priority,name").unwrap();
+/// assert_eq!(
+///     query.sort.fields()[0],
+///     SortField { field: "priority", direction: SortDirection::Desc },
+/// );
+///
+/// let mut issues = vec![Issue { name: "b", priority: 1 }, Issue { name: "a", priority: 1 }, Issue { name: "c", priority: 2 }];
+/// query.sort.sort(&mut issues);
+/// assert_eq!(issues.iter().map(|issue| issue.name).collect::<Vec<_>>(), ["c", "a", "b"]);
+///
+/// assert!(actix_web::web::Query::<ListQuery>::from_query("limit=10&sort=title").is_err());
+/// ```
+pub struct SortQuery<M> {
+    fields: Vec<SortField>,
+    model: PhantomData<fn() -> M>,
+}
+
+impl<M> SortQuery<M> {
+    /// The fields to sort by, in order
+    pub fn fields(&self) -> &[SortField] {
+        &self.fields
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Sorts `items` by the JSON serialization of their fields, the values of different types ordered `null` first,
+    /// then the booleans, the numbers, the strings, the arrays and the objects,
+    /// keeping the order of the items which compare equal
+    pub fn sort<T: Serialize>(&self, items: &mut [T]) {
+        if self.is_empty() {
+            return;
+        }
+        items.sort_by_cached_key(|item| {
+            let item = serde_json::to_value(item).unwrap_or(Value::Null);
+            let values = self
+                .fields
+                .iter()
+                .map(|sort| (item.get(sort.field).cloned().unwrap_or(Value::Null), sort.direction))
+                .collect();
+            SortKey(values)
+        });
+    }
+
+    fn parse(fields: &[&'static str], sort: &str) -> Result<Vec<SortField>, String> {
+        let mut sort_fields: Vec<SortField> = Vec::new();
+        for name in sort.split(',').filter(|name| !name.is_empty()) {
+            let (name, direction) = match name.strip_prefix('-') {
+                Some(name) => (name, SortDirection::Desc),
+                None => (name, SortDirection::Asc),
+            };
+            let field = fields.iter().find(|field| **field == name).ok_or_else(|| {
+                format!("unknown sort field `{}`, expected one of `{}`", name, fields.join("`, `"))
+            })?;
+            if sort_fields.iter().any(|sort| sort.field == name) {
+                return Err(format!("duplicate sort field `{}`", name));
+            }
+            sort_fields.push(SortField { field, direction });
+        }
+        Ok(sort_fields)
+    }
+}
+
+/// The values of the sorted fields of an item, compared as JSON values
+struct SortKey(Vec<(Value, SortDirection)>);
+
+impl Ord for SortKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0
+            .iter()
+            .zip(&other.0)
+            .map(|((value, direction), (other, _))| match direction {
+                SortDirection::Asc => compare(value, other),
+                SortDirection::Desc => compare(other, value),
+            })
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for SortKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for SortKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for SortKey {}
+
+/// The ordering of two JSON values of a field, a total order ranking their types first :
+/// `null` < booleans < numbers < strings < arrays < objects
+fn compare(value: &Value, other: &Value) -> Ordering {
+    match (value, other) {
+        (Value::Bool(value), Value::Bool(other)) => value.cmp(other),
+        (Value::Number(value), Value::Number(other)) => compare_numbers(value, other),
+        (Value::String(value), Value::String(other)) => value.cmp(other),
+        (Value::Array(values), Value::Array(others)) => compare_all(values.iter(), others.iter(), compare),
+        (Value::Object(values), Value::Object(others)) => {
+            compare_all(values.iter(), others.iter(), |(key, value), (other_key, other)| {
+                key.cmp(other_key).then_with(|| compare(value, other))
+            })
+        }
+        _ => rank(value).cmp(&rank(other)),
+    }
+}
+
+/// The rank of the type of a JSON value in the order of `compare`
+fn rank(value: &Value) -> u8 {
+    match value {
+        Value::Null => 0,
+        Value::Bool(_) => 1,
+        Value::Number(_) => 2,
+        Value::String(_) => 3,
+        Value::Array(_) => 4,
+        Value::Object(_) => 5,
+    }
+}
+
+/// The ordering of two JSON numbers, exact on the integers, with `f64::total_cmp` otherwise
+fn compare_numbers(value: &Number, other: &Number) -> Ordering {
+    if let (Some(value), Some(other)) = (value.as_i64(), other.as_i64()) {
+        return value.cmp(&other);
+    }
+    if let (Some(value), Some(other)) = (value.as_u64(), other.as_u64()) {
+        return value.cmp(&other);
+    }
+    let (value, other) = (value.as_f64().unwrap_or(f64::NAN), other.as_f64().unwrap_or(f64::NAN));
+    value.total_cmp(&other)
+}
+
+/// The lexicographic ordering of two sequences, by `compare` on their elements
+fn compare_all<T>(
+    mut values: impl Iterator<Item = T>,
+    mut others: impl Iterator<Item = T>,
+    compare: impl Fn(T, T) -> Ordering,
+) -> Ordering {
+    loop {
+        match (values.next(), others.next()) {
+            (Some(value), Some(other)) => match compare(value, other) {
+                Ordering::Equal => continue,
+                ordering => return ordering,
+            },
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+        }
+    }
+}
+
+impl<M> Default for SortQuery<M> {
+    fn default() -> Self {
+        SortQuery { fields: Vec::new(), model: PhantomData }
+    }
+}
+
+impl<M> Clone for SortQuery<M> {
+    fn clone(&self) -> Self {
+        SortQuery { fields: self.fields.clone(), model: PhantomData }
+    }
+}
+
+impl<M> fmt::Debug for SortQuery<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SortQuery").field("fields", &self.fields).finish()
+    }
+}
+
+impl<'de, M: Sortable> Deserialize<'de> for SortQuery<M> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_params(deserializer, SortQuery::default(), |query: &mut SortQuery<M>, key, value| {
+            if key == "sort" {
+                query.fields = SortQuery::<M>::parse(M::sort_fields(), &value)?;
+            }
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,52 @@
+use crate::json::value_error;
+use crate::{json_payload, parse_json, RestError};
+use actix_web::HttpRequest;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// Builds the payload of an update from the JSON body of `req`.
+///
+/// A body carrying an `id` which differs from the id of the path is answered 409 Conflict, see `check_body_id`.
+/// A body which does not deserialize is answered as with `json_payload`.
+pub fn update_payload<T, I>(req: &HttpRequest, body: &[u8], path_id: &I) -> Result<T, RestError>
+where
+    T: DeserializeOwned,
+    I: DeserializeOwned + PartialEq,
+{
+    let document: Value = json_payload(req, body)?;
+    check_body_id(&document, path_id)?;
+    // parsed again from the body, to locate its errors
+    parse_json(body)
+}
+
+/// Checks the `id` of the document of an update, or of a patched entity, against the id of the path.
+///
+/// An `id` which differs is answered 409 Conflict, the document being inconsistent with the entity it replaces
+/// (RFC 7231, section 4.3.4). An `id` which is not an id at all, e.g. `"1"` for an integer id, is answered
+/// 400 Bad Request locating it at `/id`. A document without `id`, or with a null one, is accepted.
+///
+/// ```
+/// use actix_restful::RestError;
+/// use serde_json::json;
+///
+/// assert!(actix_restful::check_body_id(&json!({ "id": 1, "content": "test" }), &1).is_ok());
+/// assert!(actix_restful::check_body_id(&json!({ "content": "test" }), &1).is_ok());
+/// assert!(matches!(actix_restful::check_body_id(&json!({ "id": 7 }), &1), Err(RestError::Conflict(_))));
+/// assert!(matches!(actix_restful::check_body_id(&json!({ "id": "1" }), &1), Err(RestError::InvalidBody(_))));
+/// ```
+pub fn check_body_id<I>(document: &Value, path_id: &I) -> Result<(), RestError>
+where
+    I: DeserializeOwned + PartialEq,
+{
+    match document.get("id") {
+        None | Some(Value::Null) => Ok(()),
+        Some(body_id) => match I::deserialize(body_id) {
+            Ok(id) if id == *path_id => Ok(()),
+            Ok(_) => Err(RestError::Conflict(format!(
+                "the id {} of the body does not match the id of the path",
+                body_id
+            ))),
+            Err(err) => Err(RestError::InvalidBody(Box::new(value_error("/id", &err)))),
+        },
+    }
+}
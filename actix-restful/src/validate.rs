@@ -0,0 +1,214 @@
+use crate::RestError;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// Checks a payload before the generated handlers call `save`, `update` or `patch`,
+/// a payload failing it is answered 422 Unprocessable Entity with the list of its invalid fields.
+///
+/// Derive it with the rules of the fields, or implement it for custom checks :
+///
+/// ```ignore
+/// #[derive(Deserialize, HttpCreate, Validate)]
+/// struct NewItem {
+///     #[restful(length(min = 1, max = 200))]
+///     content: String,
+///     #[restful(email)]
+///     contact: Option<String>,
+///     #[restful(range(min = 0, max = 5))]
+///     priority: u8,
+///     #[restful(custom = "check_sku")]
+///     sku: String,
+/// }
+///
+/// // the rules of a custom check answer the message of the error
+/// fn check_sku(sku: &str) -> Result<(), String> {
+/// }
+/// ```
+///
+/// The rules of an `Option` field are checked when it is `Some`. The errors name the fields after their `rename`
+/// or `rename_all` serde attributes, `r#type` as `type`. The payloads which don't implement
+/// `Validate` are handed to the model methods as they are.
+pub trait Validate {
+    fn validate(&self) -> Result<(), ValidationErrors>;
+}
+
+/// An invalid field of a payload, listed in the `errors` member of the problem document :
+/// a field failing `Validate`, answered 422, or a value of a body which does not deserialize, answered 400
+#[derive(Clone, Debug, Serialize)]
+pub struct FieldError {
+    /// The name of the field as serde serializes it, or the JSON Pointer of the value in the body
+    pub field: String,
+    /// The rule the field fails, `length`, `email`, `range` or `custom`,
+    /// or the kind of deserialization error, e.g. `invalid-type` or `missing-field`
+    pub code: String,
+    pub message: String,
+    /// The type expected by the deserialization
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    /// The position of the deserialization error in the body
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+}
+
+/// The invalid fields of a payload, see `Validate`
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(transparent)]
+pub struct ValidationErrors {
+    errors: Vec<FieldError>,
+}
+
+impl ValidationErrors {
+    pub fn add(&mut self, field: &str, code: &str, message: String) {
+        self.errors.push(FieldError {
+            field: field.to_string(),
+            code: code.to_string(),
+            message,
+            expected: None,
+            line: None,
+            column: None,
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn errors(&self) -> &[FieldError] {
+        &self.errors
+    }
+
+    /// `Ok` when no field is invalid
+    pub fn into_result(self) -> Result<(), ValidationErrors> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut fields: Vec<&str> = self.errors.iter().map(|err| err.field.as_str()).collect();
+        fields.dedup();
+        write!(f, "invalid fields : {}", fields.join(", "))
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+impl From<ValidationErrors> for RestError {
+    fn from(errors: ValidationErrors) -> Self {
+        RestError::Invalid(errors)
+    }
+}
+
+/// The length checked by the `length` rule of `Validate` : the number of characters of a string,
+/// the number of items of a collection
+pub trait Length {
+    fn length(&self) -> usize;
+}
+
+impl Length for str {
+    fn length(&self) -> usize {
+        self.chars().count()
+    }
+}
+
+impl Length for String {
+    fn length(&self) -> usize {
+        self.as_str().length()
+    }
+}
+
+impl<T> Length for [T] {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Length for Vec<T> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V, S> Length for HashMap<K, V, S> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V> Length for BTreeMap<K, V> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+/// The `length` rule of `Validate`
+pub fn validate_length<T: Length + ?Sized>(value: &T, min: Option<usize>, max: Option<usize>) -> Result<(), String> {
+    let length = value.length();
+    match (min, max) {
+        (Some(min), _) if length < min => Err(format!("the length must be at least {}", min)),
+        (_, Some(max)) if length > max => Err(format!("the length must be at most {}", max)),
+        _ => Ok(()),
+    }
+}
+
+/// The `email` rule of `Validate`, a loose check of the shape `local@domain.tld`
+pub fn validate_email<T: AsRef<str> + ?Sized>(value: &T) -> Result<(), String> {
+    let value = value.as_ref();
+    let valid = match value.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.split('.').count() > 1
+                && domain.split('.').all(|label| !label.is_empty())
+                && !value.chars().any(char::is_whitespace)
+        }
+        None => false,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(String::from("must be a valid email address"))
+    }
+}
+
+/// The `range` rule of `Validate`, the field is converted with `as f64`
+pub fn validate_range(value: f64, min: Option<f64>, max: Option<f64>) -> Result<(), String> {
+    match (min, max) {
+        (Some(min), _) if value < min => Err(format!("must be at least {}", min)),
+        (_, Some(max)) if value > max => Err(format!("must be at most {}", max)),
+        _ => Ok(()),
+    }
+}
+
+/// Runs the `Validate` implementation of a payload in the generated handlers, if it has one :
+/// `ValidatePayload` applies to the payloads implementing `Validate`, `SkipValidation`
+/// is found after an auto-ref for the others.
+#[doc(hidden)]
+pub struct Validation<'a, T>(pub &'a T);
+
+#[doc(hidden)]
+pub trait ValidatePayload {
+    fn validate_payload(&self) -> Result<(), RestError>;
+}
+
+impl<T: Validate> ValidatePayload for Validation<'_, T> {
+    fn validate_payload(&self) -> Result<(), RestError> {
+        self.0.validate().map_err(RestError::Invalid)
+    }
+}
+
+#[doc(hidden)]
+pub trait SkipValidation {
+    fn validate_payload(&self) -> Result<(), RestError> {
+        Ok(())
+    }
+}
+
+impl<T> SkipValidation for &Validation<'_, T> {}
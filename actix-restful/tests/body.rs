@@ -0,0 +1,128 @@
+use actix_restful::{Findable, NewModel, NoQuery, RequestContext, Resource, RestfulPathInfo, UpdatableModel};
+use actix_restful_derive::{actix_restful_info, HttpCreate, HttpFind, HttpUpdate};
+use actix_web::http::{header, StatusCode};
+use actix_web::{test, web, App};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+#[derive(Serialize, Deserialize, HttpFind, HttpCreate)]
+#[actix_restful_info(scope = "/v1", path = "item", body_limit = 256)]
+struct Item {
+    id: i64,
+    content: String,
+    tags: Vec<String>,
+}
+
+#[async_trait]
+impl Findable for Item {
+    type Id = i64;
+    type FindQuery = NoQuery;
+    type State = ();
+
+    async fn find(id: i64, _query: &NoQuery, _state: &(), _ctx: &RequestContext) -> Result<Box<Item>> {
+        Ok(Box::new(Item { id, content: String::from("found"), tags: Vec::new() }))
+    }
+}
+
+#[async_trait]
+impl NewModel for Item {
+    type Output = Item;
+    type Query = NoQuery;
+    type State = ();
+
+    async fn save(self, _query: &NoQuery, _state: &(), _ctx: &RequestContext) -> Result<Item> {
+        Ok(self)
+    }
+}
+
+#[derive(Serialize, Deserialize, HttpUpdate)]
+struct UpdatableItem {
+    content: String,
+}
+
+#[async_trait]
+impl UpdatableModel for UpdatableItem {
+    type Model = Item;
+    type Output = UpdatableItem;
+    type Query = NoQuery;
+    type State = ();
+
+    async fn update(
+        self,
+        _id: i64,
+        _current: Item,
+        _query: &NoQuery,
+        _state: &(),
+        _ctx: &RequestContext,
+    ) -> Result<UpdatableItem> {
+        Ok(self)
+    }
+}
+
+/// The status and the json body of the response to `req`, on the routes registered by `resource`
+macro_rules! call {
+    ($req:expr) => {
+        call!($req, Resource::<Item>::new())
+    };
+    ($req:expr, $resource:expr) => {{
+        let resource = $resource.find().create::<Item>().update::<UpdatableItem>().configure();
+        let mut app = test::init_service(App::new().service(web::scope(Item::scope()).configure(resource))).await;
+        let res = test::call_service(&mut app, $req.to_request()).await;
+        let status = res.status();
+        let body: Value = serde_json::from_slice(&test::read_body(res).await).unwrap();
+        (status, body)
+    }};
+}
+
+fn post(body: &str) -> test::TestRequest {
+    test::TestRequest::post()
+        .uri("/v1/item")
+        .header(header::CONTENT_TYPE, "application/json")
+        .set_payload(body.to_string())
+}
+
+#[actix_rt::test]
+async fn invalid_bodies_point_at_the_value() {
+    let (status, body) = call!(post(r#"{ "id": 1, "content": "new", "tags": ["a", 2] }"#));
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["errors"][0]["field"], "/tags/1");
+    assert_eq!(body["errors"][0]["code"], "invalid-type");
+    assert_eq!(body["errors"][0]["expected"], "a string");
+    assert_eq!(body["errors"][0]["line"], 1);
+    assert_eq!(body["errors"][0]["column"], 44);
+
+    let (status, body) = call!(post(r#"{ "id": 1, "tags": [] }"#));
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["errors"][0]["field"], "/content");
+    assert_eq!(body["errors"][0]["code"], "missing-field");
+
+    let req = test::TestRequest::put()
+        .uri("/v1/item/1")
+        .header(header::CONTENT_TYPE, "application/json")
+        .set_payload(r#"{ "content": 2 }"#);
+    let (status, body) = call!(req);
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["errors"][0]["field"], "/content");
+}
+
+#[actix_rt::test]
+async fn malformed_json() {
+    let (status, body) = call!(post(r#"{ "id": 1, "content": "#));
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["errors"][0]["code"], "invalid-json");
+}
+
+#[actix_rt::test]
+async fn body_limits() {
+    let item = json!({ "id": 1, "content": "x".repeat(300), "tags": [] });
+    let (status, body) = call!(test::TestRequest::post().uri("/v1/item").set_json(&item));
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(body["status"], 413);
+
+    // the limit of the model overridden by the resource
+    let req = test::TestRequest::post().uri("/v1/item").set_json(&item);
+    let (status, _) = call!(req, Resource::<Item>::new().body_limit(1024));
+    assert_eq!(status, StatusCode::CREATED);
+}
@@ -0,0 +1,15 @@
+use actix_restful::RequestContext;
+use actix_web::test::TestRequest;
+
+#[test]
+fn debug_redacts_the_credentials() {
+    let req = TestRequest::get()
+        .uri("/v1/item/1")
+        .header("authorization", "Bearer secret-token")
+        .header("cookie", "session=secret-session")
+        .header("accept-language", "fr")
+        .to_http_request();
+    let debug = format!("{:?}", RequestContext::from_req(&req));
+    assert!(!debug.contains("secret"), "{}", debug);
+    assert!(debug.contains("\"authorization\": \"<redacted>\"") && debug.contains("\"fr\""), "{}", debug);
+}
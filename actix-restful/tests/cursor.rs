@@ -0,0 +1,151 @@
+use actix_restful::{
+    CursorPage, CursorQuery, Findable, Listable, NestedListable, NoQuery, RequestContext, Resource, RestfulConfig,
+    RestfulPathInfo, SortQuery,
+};
+use actix_restful_derive::{actix_restful_info, HttpFind, HttpList};
+use actix_web::http::StatusCode;
+use actix_web::{test, web, App};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+struct EventQuery {
+    #[serde(flatten)]
+    sort: SortQuery<Event>,
+    #[serde(flatten)]
+    page: CursorQuery<2, 10>,
+}
+
+/// The events `0..5` following the cursor, in the order of the ids
+fn events(query: &EventQuery, ctx: &RequestContext) -> Result<CursorPage<Event>> {
+    let after: Option<i64> = query.page.after(ctx)?;
+    let mut events: Vec<Event> =
+        (0..5).filter(|id| after.is_none_or(|after| *id > after)).map(|id| Event { id }).collect();
+    query.sort.sort(&mut events);
+    Ok(CursorPage::new(events, &query.page, ctx, |event| event.id)?)
+}
+
+#[derive(Serialize, HttpList)]
+#[actix_restful_info(scope = "/v1", path = "event", sort = "id")]
+struct Event {
+    id: i64,
+}
+
+#[async_trait]
+impl Listable for Event {
+    type ListQuery = EventQuery;
+    type ListResult = CursorPage<Event>;
+    type State = ();
+
+    async fn list(query: &EventQuery, _state: &(), ctx: &RequestContext) -> Result<CursorPage<Event>> {
+        events(query, ctx)
+    }
+}
+
+/// The same list, on another route
+#[derive(Serialize, HttpList)]
+#[actix_restful_info(scope = "/v1", path = "archived_event")]
+struct ArchivedEvent {}
+
+#[async_trait]
+impl Listable for ArchivedEvent {
+    type ListQuery = EventQuery;
+    type ListResult = CursorPage<Event>;
+    type State = ();
+
+    async fn list(query: &EventQuery, _state: &(), ctx: &RequestContext) -> Result<CursorPage<Event>> {
+        events(query, ctx)
+    }
+}
+
+#[derive(Serialize, HttpFind)]
+#[actix_restful_info(scope = "/v1", path = "project")]
+struct Project {
+    id: i64,
+}
+
+#[async_trait]
+impl Findable for Project {
+    type Id = i64;
+    type FindQuery = NoQuery;
+    type State = ();
+
+    async fn find(id: i64, _query: &NoQuery, _state: &(), _ctx: &RequestContext) -> Result<Box<Project>> {
+        Ok(Box::new(Project { id }))
+    }
+}
+
+/// The events of a project
+#[derive(Serialize, HttpList)]
+#[actix_restful_info(scope = "/v1", path = "event", parent = "Project")]
+struct ProjectEvent {}
+
+#[async_trait]
+impl NestedListable for ProjectEvent {
+    type ParentId = i64;
+    type ListQuery = EventQuery;
+    type ListResult = CursorPage<Event>;
+    type State = ();
+
+    async fn list(_project_id: i64, query: &EventQuery, _state: &(), ctx: &RequestContext) -> Result<CursorPage<Event>> {
+        events(query, ctx)
+    }
+}
+
+#[actix_rt::test]
+async fn cursors_are_bound_to_their_list() {
+    let mut app = test::init_service(
+        App::new()
+            .app_data(RestfulConfig::default().cursor_key("secret"))
+            .service(
+                web::scope(Event::scope())
+                    .configure(Resource::<Event>::new().list().configure())
+                    .configure(Resource::<ArchivedEvent>::new().list().configure()),
+            ),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/v1/event?sort=id").to_request();
+    let page: serde_json::Value = test::read_response_json(&mut app, req).await;
+    let cursor = page["next_cursor"].as_str().unwrap().to_string();
+
+    let req = test::TestRequest::get().uri(&format!("/v1/event?sort=id&limit=3&cursor={}", cursor)).to_request();
+    let page: serde_json::Value = test::read_response_json(&mut app, req).await;
+    assert_eq!(page["items"], serde_json::json!([{ "id": 2 }, { "id": 3 }, { "id": 4 }]));
+
+    let (payload, signature) = cursor.split_once('.').unwrap();
+    let tampered = format!("{}.{}", base64::encode_config(b"0", base64::URL_SAFE_NO_PAD), signature);
+    assert_ne!(payload, tampered.split_once('.').unwrap().0);
+    for uri in [
+        format!("/v1/event?sort=id&cursor={}", tampered),
+        format!("/v1/event?sort=-id&cursor={}", cursor),
+        format!("/v1/event?cursor={}", cursor),
+        format!("/v1/archived_event?sort=id&cursor={}", cursor),
+    ] {
+        let res = test::call_service(&mut app, test::TestRequest::get().uri(&uri).to_request()).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST, "{}", uri);
+    }
+}
+
+#[actix_rt::test]
+async fn cursors_are_bound_to_their_parent() {
+    let mut app = test::init_service(
+        App::new().app_data(RestfulConfig::default().cursor_key("secret")).service(
+            web::scope(ProjectEvent::scope()).configure(Resource::<ProjectEvent>::new().list().configure()),
+        ),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/v1/project/1/event").to_request();
+    let page: serde_json::Value = test::read_response_json(&mut app, req).await;
+    let cursor = page["next_cursor"].as_str().unwrap().to_string();
+
+    let req = test::TestRequest::get().uri(&format!("/v1/project/1/event?cursor={}", cursor)).to_request();
+    let res = test::call_service(&mut app, req).await;
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let req = test::TestRequest::get().uri(&format!("/v1/project/2/event?cursor={}", cursor)).to_request();
+    let res = test::call_service(&mut app, req).await;
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+}
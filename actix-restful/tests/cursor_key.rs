@@ -0,0 +1,36 @@
+use actix_restful::{CursorPage, CursorQuery, Listable, RequestContext, Resource, RestfulPathInfo};
+use actix_restful_derive::{actix_restful_info, HttpList};
+use actix_web::{web, App};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+struct EventQuery {
+    #[serde(flatten)]
+    page: CursorQuery,
+}
+
+#[derive(Serialize, HttpList)]
+#[actix_restful_info(scope = "/v1", path = "event")]
+struct Event {
+    id: i64,
+}
+
+#[async_trait]
+impl Listable for Event {
+    type ListQuery = EventQuery;
+    type ListResult = CursorPage<Event>;
+    type State = ();
+
+    async fn list(query: &EventQuery, _state: &(), ctx: &RequestContext) -> Result<CursorPage<Event>> {
+        Ok(CursorPage::new(Vec::new(), &query.page, ctx, |event: &Event| event.id)?)
+    }
+}
+
+// in its own test binary : no `RestfulConfig::cursor_key` is ever set in this process
+#[test]
+#[should_panic(expected = "the list of `/event` answers cursors, which require a secret")]
+fn lists_of_cursors_require_a_secret() {
+    App::new().service(web::scope(Event::scope()).configure(Resource::<Event>::new().list().configure()));
+}
@@ -0,0 +1,90 @@
+use actix_restful::{Findable, NoQuery, RequestContext, Resource, RestError, RestfulConfig, RestfulPathInfo};
+use actix_restful_derive::{actix_restful_info, HttpFind};
+use actix_web::http::{header, StatusCode};
+use actix_web::{test, web, App};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Serialize, HttpFind)]
+#[actix_restful_info(scope = "/v1", path = "item")]
+struct Item {
+    id: i64,
+}
+
+#[async_trait]
+impl Findable for Item {
+    type Id = i64;
+    type FindQuery = NoQuery;
+    type State = ();
+
+    async fn find(id: i64, _query: &NoQuery, _state: &(), _ctx: &RequestContext) -> Result<Box<Item>> {
+        match id {
+            1 => Ok(Box::new(Item { id })),
+            2 => Err(RestError::Conflict(String::from("item 2 is locked")).into()),
+            3 => Err(anyhow!("connection refused by db.internal:5432")),
+            _ => Err(RestError::NotFound(format!("item {} does not exist", id)).into()),
+        }
+    }
+}
+
+/// The status and the json body of the response to a GET on `uri`
+macro_rules! get {
+    ($uri:expr) => {{
+        let resource = Resource::<Item>::new().find().configure();
+        let mut app = test::init_service(App::new().service(web::scope(Item::scope()).configure(resource))).await;
+        let res = test::call_service(&mut app, test::TestRequest::get().uri($uri).to_request()).await;
+        let status = res.status();
+        let body: Value = serde_json::from_slice(&test::read_body(res).await).unwrap();
+        (status, body)
+    }};
+}
+
+#[actix_rt::test]
+async fn found() {
+    let (status, body) = get!("/v1/item/1");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["id"], 1);
+}
+
+#[actix_rt::test]
+async fn not_found() {
+    let (status, body) = get!("/v1/item/4");
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["status"], 404);
+    assert_eq!(body["detail"], "item 4 does not exist");
+    assert_eq!(body["type"], "about:blank");
+}
+
+#[actix_rt::test]
+async fn conflict() {
+    let (status, body) = get!("/v1/item/2");
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["status"], 409);
+    assert_eq!(body["detail"], "item 2 is locked");
+}
+
+#[actix_rt::test]
+async fn internal_errors_hide_their_detail() {
+    let (status, body) = get!("/v1/item/3");
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(body["status"], 500);
+    assert_eq!(body["detail"], "internal server error");
+    assert!(!body.to_string().contains("db.internal"));
+}
+
+#[actix_rt::test]
+async fn problem_type_hook() {
+    let config = RestfulConfig::default().problem_type(|err| format!("https://api.example.com/problems/{}", err.kind()));
+    let resource = Resource::<Item>::new().find().configure();
+    let mut app =
+        test::init_service(App::new().app_data(config).service(web::scope(Item::scope()).configure(resource))).await;
+    let res = test::call_service(&mut app, test::TestRequest::get().uri("/v1/item/4").to_request()).await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    assert_eq!(res.headers().get(header::CONTENT_TYPE).unwrap(), "application/problem+json");
+    let body: Value = serde_json::from_slice(&test::read_body(res).await).unwrap();
+    assert_eq!(body["type"], "https://api.example.com/problems/not-found");
+    assert_eq!(body["title"], "Not Found");
+    assert_eq!(body["instance"], "/v1/item/4");
+}
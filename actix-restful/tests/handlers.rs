@@ -0,0 +1,128 @@
+use actix_restful::{
+    Findable, NoQuery, PatchableModel, RequestContext, RestError, RestfulPathInfo, RestfulRoutes, UpdatableModel,
+};
+use actix_restful_derive::Restful;
+use actix_web::http::{header, StatusCode};
+use actix_web::{test, web, App};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+#[derive(Serialize, Deserialize, Restful)]
+#[restful(scope = "/v1", path = "item", find, update = UpdatableItem, patch = UpdatableItem)]
+struct Item {
+    id: String,
+    content: String,
+}
+
+#[async_trait]
+impl Findable for Item {
+    type Id = String;
+    type FindQuery = NoQuery;
+    type State = ();
+
+    async fn find(id: String, _query: &NoQuery, _state: &(), _ctx: &RequestContext) -> Result<Box<Item>> {
+        match id.as_str() {
+            "missing" => Err(RestError::NotFound(format!("no item {}", id)).into()),
+            _ => Ok(Box::new(Item { id, content: String::from("stored") })),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct UpdatableItem {
+    id: String,
+    content: String,
+}
+
+#[async_trait]
+impl UpdatableModel for UpdatableItem {
+    type Model = Item;
+    type Output = UpdatableItem;
+    type Query = NoQuery;
+    type State = ();
+
+    async fn update(
+        self,
+        _id: String,
+        _current: Item,
+        _query: &NoQuery,
+        _state: &(),
+        _ctx: &RequestContext,
+    ) -> Result<UpdatableItem> {
+        Ok(self)
+    }
+}
+
+#[async_trait]
+impl PatchableModel for UpdatableItem {
+    type Model = Item;
+    type Output = UpdatableItem;
+    type Query = NoQuery;
+    type State = ();
+
+    async fn patch(self, _id: String, _query: &NoQuery, _state: &(), _ctx: &RequestContext) -> Result<UpdatableItem> {
+        Ok(self)
+    }
+}
+
+/// The service answering the routes of `Item`
+macro_rules! app {
+    () => {
+        test::init_service(App::new().service(web::scope(Item::scope()).configure(Item::configure))).await
+    };
+}
+
+/// The status, the headers and the json body of the response to `req`
+macro_rules! call {
+    ($app:expr, $req:expr) => {{
+        let res = test::call_service(&mut $app, $req.to_request()).await;
+        let (status, headers) = (res.status(), res.headers().clone());
+        let body: Value = serde_json::from_slice(&test::read_body(res).await).unwrap_or(Value::Null);
+        (status, headers, body)
+    }};
+}
+
+#[actix_rt::test]
+async fn update_rejects_another_id() {
+    let mut app = app!();
+    let req = test::TestRequest::put().uri("/v1/item/1").set_json(&json!({ "id": "2", "content": "updated" }));
+    let (status, headers, body) = call!(app, req);
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(headers.get(header::CONTENT_TYPE).unwrap(), "application/problem+json");
+    assert_eq!(body["status"], 409);
+
+    let req = test::TestRequest::put().uri("/v1/item/1").set_json(&json!({ "id": "1", "content": "updated" }));
+    let (status, _, body) = call!(app, req);
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["content"], "updated");
+}
+
+#[actix_rt::test]
+async fn ids_of_another_type_are_bad_requests() {
+    let mut app = app!();
+    let req = test::TestRequest::put().uri("/v1/item/1").set_json(&json!({ "id": 1, "content": "updated" }));
+    let (status, _, body) = call!(app, req);
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["errors"][0]["field"], "/id");
+    assert_eq!(body["errors"][0]["code"], "invalid-type");
+
+    let (status, _, body) = call!(app, test::TestRequest::patch().uri("/v1/item/1").set_json(&json!({ "id": 1 })));
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["errors"][0]["field"], "/id");
+}
+
+#[actix_rt::test]
+async fn patch_rejects_another_id() {
+    let mut app = app!();
+    let (status, _, _) = call!(app, test::TestRequest::patch().uri("/v1/item/1").set_json(&json!({ "id": "2" })));
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let req = test::TestRequest::patch()
+        .uri("/v1/item/1")
+        .header(header::CONTENT_TYPE, "application/json-patch+json")
+        .set_payload(r#"[{ "op": "replace", "path": "/id", "value": "2" }]"#);
+    let (status, _, _) = call!(app, req);
+    assert_eq!(status, StatusCode::CONFLICT);
+}
@@ -0,0 +1,151 @@
+use actix_restful::{gen_endpoint, Findable, NoQuery, RequestContext, RestfulPathInfo};
+use actix_restful_derive::{actix_restful_info, HttpFind};
+use actix_web::http::StatusCode;
+use actix_web::{test, web, App};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fmt;
+use uuid::Uuid;
+
+#[derive(Serialize, HttpFind)]
+#[actix_restful_info(scope = "/v1", path = "item")]
+struct Item {
+    id: i64,
+}
+
+#[async_trait]
+impl Findable for Item {
+    type Id = i64;
+    type FindQuery = NoQuery;
+    type State = ();
+
+    async fn find(id: i64, _query: &NoQuery, _state: &(), _ctx: &RequestContext) -> Result<Box<Item>> {
+        Ok(Box::new(Item { id }))
+    }
+}
+
+#[derive(Serialize, HttpFind)]
+#[actix_restful_info(scope = "/v1", path = "token")]
+struct Token {
+    id: Uuid,
+}
+
+#[async_trait]
+impl Findable for Token {
+    type Id = Uuid;
+    type FindQuery = NoQuery;
+    type State = ();
+
+    async fn find(id: Uuid, _query: &NoQuery, _state: &(), _ctx: &RequestContext) -> Result<Box<Token>> {
+        Ok(Box::new(Token { id }))
+    }
+}
+
+#[derive(Serialize, HttpFind)]
+#[actix_restful_info(scope = "/v1", path = "category")]
+struct Category {
+    id: String,
+}
+
+#[async_trait]
+impl Findable for Category {
+    type Id = String;
+    type FindQuery = NoQuery;
+    type State = ();
+
+    async fn find(id: String, _query: &NoQuery, _state: &(), _ctx: &RequestContext) -> Result<Box<Category>> {
+        Ok(Box::new(Category { id }))
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct ProductKey {
+    tenant: String,
+    code: u32,
+}
+
+impl fmt::Display for ProductKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.tenant, self.code)
+    }
+}
+
+#[derive(Serialize, HttpFind)]
+#[actix_restful_info(scope = "/v1", path = "product", id_pattern = "{tenant}/{code}")]
+struct Product {
+    id: ProductKey,
+}
+
+#[async_trait]
+impl Findable for Product {
+    type Id = ProductKey;
+    type FindQuery = NoQuery;
+    type State = ();
+
+    async fn find(id: ProductKey, _query: &NoQuery, _state: &(), _ctx: &RequestContext) -> Result<Box<Product>> {
+        Ok(Box::new(Product { id }))
+    }
+}
+
+/// The status and the json body of the response to a GET on `uri`
+macro_rules! get {
+    ($uri:expr) => {{
+        let mut app = test::init_service(
+            App::new().service(
+                web::scope(Item::scope())
+                    .configure(gen_endpoint!(Item => find))
+                    .configure(gen_endpoint!(Token => find))
+                    .configure(gen_endpoint!(Category => find))
+                    .configure(gen_endpoint!(Product => find)),
+            ),
+        )
+        .await;
+        let res = test::call_service(&mut app, test::TestRequest::get().uri($uri).to_request()).await;
+        let status = res.status();
+        let body: Value = serde_json::from_slice(&test::read_body(res).await).unwrap();
+        (status, body)
+    }};
+}
+
+#[actix_rt::test]
+async fn integer_ids() {
+    let (status, body) = get!("/v1/item/42");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "id": 42 }));
+}
+
+#[actix_rt::test]
+async fn uuid_ids() {
+    let (status, body) = get!("/v1/token/67e55044-10b1-426f-9247-bb680e5fe0c8");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "id": "67e55044-10b1-426f-9247-bb680e5fe0c8" }));
+}
+
+#[actix_rt::test]
+async fn slug_ids() {
+    let (status, body) = get!("/v1/category/garden-tools");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "id": "garden-tools" }));
+
+    let (status, body) = get!("/v1/category/hand%20tools");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "id": "hand tools" }));
+}
+
+#[actix_rt::test]
+async fn composite_ids() {
+    let (status, body) = get!("/v1/product/acme/42");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "id": { "tenant": "acme", "code": 42 } }));
+}
+
+#[actix_rt::test]
+async fn ids_which_do_not_parse_are_bad_requests() {
+    for uri in ["/v1/item/forty-two", "/v1/token/67e55044", "/v1/product/acme/X1"] {
+        let (status, body) = get!(uri);
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", uri);
+        assert_eq!(body["status"], 400);
+    }
+}
@@ -0,0 +1,237 @@
+use actix_restful::{
+    gen_endpoint, Findable, NestedDeletable, NestedFindable, NestedListable, NestedNewModel, NestedUpdatableModel,
+    NoQuery, RequestContext, RestError, RestfulPathInfo,
+};
+use actix_restful_derive::{actix_restful_info, HttpCreate, HttpFind, HttpFindListDelete, HttpUpdate, Validate};
+use actix_web::http::{header, StatusCode};
+use actix_web::{test, web, App};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// The titles of the tasks of the projects, by project id and task id
+struct Store {
+    projects: Mutex<HashMap<i64, HashMap<i64, String>>>,
+}
+
+#[derive(Serialize, HttpFind)]
+#[actix_restful_info(scope = "/v1", path = "project")]
+struct Project {
+    id: i64,
+}
+
+#[async_trait]
+impl Findable for Project {
+    type Id = i64;
+    type FindQuery = NoQuery;
+    type State = Store;
+
+    async fn find(id: i64, _query: &NoQuery, state: &Store, _ctx: &RequestContext) -> Result<Box<Project>> {
+        match state.projects.lock().unwrap().contains_key(&id) {
+            true => Ok(Box::new(Project { id })),
+            false => Err(RestError::NotFound(format!("project {} does not exist", id)).into()),
+        }
+    }
+}
+
+#[derive(Serialize, HttpFindListDelete)]
+#[actix_restful_info(scope = "/v1", path = "task", parent = "Project", delete_no_content)]
+struct Task {
+    id: i64,
+    project_id: i64,
+    title: String,
+}
+
+#[async_trait]
+impl NestedFindable for Task {
+    type ParentId = i64;
+    type Id = i64;
+    type FindQuery = NoQuery;
+    type State = Store;
+
+    async fn find(project_id: i64, id: i64, _query: &NoQuery, state: &Store, _ctx: &RequestContext) -> Result<Box<Task>> {
+        let projects = state.projects.lock().unwrap();
+        match projects.get(&project_id).and_then(|tasks| tasks.get(&id)) {
+            Some(title) => Ok(Box::new(Task { id, project_id, title: title.clone() })),
+            None => Err(RestError::NotFound(format!("task {} does not exist", id)).into()),
+        }
+    }
+}
+
+#[async_trait]
+impl NestedListable for Task {
+    type ParentId = i64;
+    type ListQuery = NoQuery;
+    type ListResult = Vec<Task>;
+    type State = Store;
+
+    async fn list(project_id: i64, _query: &NoQuery, state: &Store, _ctx: &RequestContext) -> Result<Vec<Task>> {
+        let projects = state.projects.lock().unwrap();
+        let mut tasks: Vec<Task> = projects[&project_id]
+            .iter()
+            .map(|(id, title)| Task { id: *id, project_id, title: title.clone() })
+            .collect();
+        tasks.sort_by_key(|task| task.id);
+        Ok(tasks)
+    }
+}
+
+#[async_trait]
+impl NestedDeletable for Task {
+    type DeleteQuery = NoQuery;
+    type DeleteResult = Task;
+
+    async fn delete(self, project_id: i64, _query: &NoQuery, state: &Store, _ctx: &RequestContext) -> Result<Task> {
+        state.projects.lock().unwrap().get_mut(&project_id).map(|tasks| tasks.remove(&self.id));
+        Ok(self)
+    }
+}
+
+#[derive(Deserialize, HttpCreate, Validate)]
+#[http_create(parent = Project)]
+struct NewTask {
+    #[restful(length(min = 1))]
+    title: String,
+}
+
+#[async_trait]
+impl NestedNewModel for NewTask {
+    type ParentId = i64;
+    type Output = Task;
+    type Query = NoQuery;
+    type State = Store;
+
+    async fn save(self, project_id: i64, _query: &NoQuery, state: &Store, _ctx: &RequestContext) -> Result<Task> {
+        let mut projects = state.projects.lock().unwrap();
+        let tasks = projects.get_mut(&project_id).unwrap();
+        let id = tasks.keys().max().copied().unwrap_or(0) + 1;
+        tasks.insert(id, self.title.clone());
+        Ok(Task { id, project_id, title: self.title })
+    }
+}
+
+#[derive(Serialize, Deserialize, HttpUpdate, Validate)]
+#[http_update(parent = Project)]
+struct UpdatableTask {
+    id: i64,
+    #[restful(length(min = 1))]
+    title: String,
+}
+
+#[async_trait]
+impl NestedUpdatableModel for UpdatableTask {
+    type ParentId = i64;
+    type Model = Task;
+    type Output = Task;
+    type Query = NoQuery;
+    type State = Store;
+
+    async fn update(
+        self,
+        project_id: i64,
+        id: i64,
+        _current: Task,
+        _query: &NoQuery,
+        state: &Store,
+        _ctx: &RequestContext,
+    ) -> Result<Task> {
+        state.projects.lock().unwrap().get_mut(&project_id).unwrap().insert(id, self.title.clone());
+        Ok(Task { id, project_id, title: self.title })
+    }
+}
+
+/// The service answering the routes of `Project` and `Task`, project 1 holding task 1
+macro_rules! app {
+    () => {{
+        let tasks = vec![(1, String::from("write"))].into_iter().collect();
+        let state = web::Data::new(Store { projects: Mutex::new(vec![(1, tasks), (2, HashMap::new())].into_iter().collect()) });
+        test::init_service(
+            App::new().app_data(state).service(
+                web::scope(Project::scope())
+                    .configure(gen_endpoint!(Project => find))
+                    .configure(gen_endpoint!(Task => find, list, create(NewTask), update(UpdatableTask), delete)),
+            ),
+        )
+        .await
+    }};
+}
+
+/// The status, the headers and the json body of the response to `req`
+macro_rules! call {
+    ($app:expr, $req:expr) => {{
+        let res = test::call_service(&mut $app, $req.to_request()).await;
+        let (status, headers) = (res.status(), res.headers().clone());
+        let body: Value = serde_json::from_slice(&test::read_body(res).await).unwrap_or(Value::Null);
+        (status, headers, body)
+    }};
+}
+
+#[actix_rt::test]
+async fn find_reads_the_parent_and_the_id() {
+    let mut app = app!();
+    let (status, _, body) = call!(app, test::TestRequest::get().uri("/v1/project/1/task/1"));
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "id": 1, "project_id": 1, "title": "write" }));
+
+    let (status, _, body) = call!(app, test::TestRequest::get().uri("/v1/project/2/task/1"));
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["detail"], "task 1 does not exist");
+}
+
+#[actix_rt::test]
+async fn missing_parents_are_not_found() {
+    let mut app = app!();
+    for req in [
+        test::TestRequest::get().uri("/v1/project/9/task/1"),
+        test::TestRequest::get().uri("/v1/project/9/task"),
+        test::TestRequest::delete().uri("/v1/project/9/task/1"),
+    ] {
+        let (status, _, body) = call!(app, req);
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["detail"], "project 9 does not exist");
+    }
+}
+
+#[actix_rt::test]
+async fn create_and_list() {
+    let mut app = app!();
+    let (status, headers, body) =
+        call!(app, test::TestRequest::post().uri("/v1/project/1/task").set_json(&json!({ "title": "test" })));
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(headers.get(header::LOCATION).unwrap(), "/v1/project/1/task/2");
+    assert_eq!(body, json!({ "id": 2, "project_id": 1, "title": "test" }));
+
+    let (status, _, body) = call!(app, test::TestRequest::get().uri("/v1/project/1/task"));
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body.as_array().unwrap().len(), 2);
+
+    let (status, _, _) = call!(app, test::TestRequest::delete().uri("/v1/project/1/task/2"));
+    assert_eq!(status, StatusCode::NO_CONTENT);
+}
+
+#[actix_rt::test]
+async fn missing_entities_are_not_found_before_the_payload_is_checked() {
+    let mut app = app!();
+    // an invalid payload
+    let (status, _, body) =
+        call!(app, test::TestRequest::post().uri("/v1/project/9/task").set_json(&json!({ "title": "" })));
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["detail"], "project 9 does not exist");
+
+    let req = test::TestRequest::put().uri("/v1/project/1/task/7").set_json(&json!({ "id": 7, "title": "" }));
+    let (status, _, body) = call!(app, req);
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["detail"], "task 7 does not exist");
+
+    // a payload of another task
+    let req = test::TestRequest::put().uri("/v1/project/9/task/1").set_json(&json!({ "id": 2, "title": "test" }));
+    let (status, _, _) = call!(app, req);
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let req = test::TestRequest::put().uri("/v1/project/1/task/1").set_json(&json!({ "id": 1, "title": "" }));
+    let (status, _, _) = call!(app, req);
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
@@ -0,0 +1,84 @@
+use actix_restful::{Listable, PageQuery, Paginated, RequestContext, RestfulPathInfo, RestfulRoutes, SortQuery};
+use actix_restful_derive::Restful;
+use actix_web::http::{header, StatusCode};
+use actix_web::{test, web, App};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+#[derive(Serialize, Restful)]
+#[restful(scope = "/v1", path = "item", list, sort = "id")]
+struct Item {
+    id: String,
+    content: String,
+}
+
+#[derive(Deserialize)]
+struct ListQuery {
+    #[serde(flatten)]
+    sort: SortQuery<Item>,
+    #[serde(flatten)]
+    page: PageQuery<2, 10>,
+}
+
+#[async_trait]
+impl Listable for Item {
+    type ListQuery = ListQuery;
+    type ListResult = Paginated<Item>;
+    type State = ();
+
+    async fn list(query: &ListQuery, _state: &(), _ctx: &RequestContext) -> Result<Paginated<Item>> {
+        let mut items: Vec<Item> =
+            (0..5).map(|id| Item { id: id.to_string(), content: String::from("listed") }).collect();
+        query.sort.sort(&mut items);
+        Ok(Paginated::slice(items, &query.page))
+    }
+}
+
+/// The status, the headers and the json body of the response to a GET on `uri`
+macro_rules! get {
+    ($uri:expr) => {{
+        let mut app = test::init_service(App::new().service(web::scope(Item::scope()).configure(Item::configure))).await;
+        let res = test::call_service(&mut app, test::TestRequest::get().uri($uri).to_request()).await;
+        let (status, headers) = (res.status(), res.headers().clone());
+        let body: Value = serde_json::from_slice(&test::read_body(res).await).unwrap();
+        (status, headers, body)
+    }};
+}
+
+#[actix_rt::test]
+async fn paginated_lists_answer_links() {
+    let (status, headers, body) = get!("/v1/item?sort=-id&offset=2");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers.get("x-total-count").unwrap(), "5");
+    assert_eq!(
+        headers.get(header::LINK).unwrap(),
+        "</v1/item?sort=-id&offset=0&limit=2>; rel=\"first\", \
+         </v1/item?sort=-id&offset=0&limit=2>; rel=\"prev\", \
+         </v1/item?sort=-id&offset=4&limit=2>; rel=\"next\", \
+         </v1/item?sort=-id&offset=4&limit=2>; rel=\"last\""
+    );
+    assert_eq!(body["items"], json!([{ "id": "2", "content": "listed" }, { "id": "1", "content": "listed" }]));
+    assert_eq!(body["total"], 5);
+}
+
+#[actix_rt::test]
+async fn the_first_and_last_pages_have_no_prev_or_next_link() {
+    let (_, headers, _) = get!("/v1/item?limit=3");
+    assert_eq!(
+        headers.get(header::LINK).unwrap(),
+        "</v1/item?offset=0&limit=3>; rel=\"first\", \
+         </v1/item?offset=3&limit=3>; rel=\"next\", \
+         </v1/item?offset=3&limit=3>; rel=\"last\""
+    );
+
+    let (_, headers, body) = get!("/v1/item?offset=3&limit=3");
+    assert_eq!(
+        headers.get(header::LINK).unwrap(),
+        "</v1/item?offset=0&limit=3>; rel=\"first\", \
+         </v1/item?offset=0&limit=3>; rel=\"prev\", \
+         </v1/item?offset=3&limit=3>; rel=\"last\""
+    );
+    assert_eq!(body["items"].as_array().unwrap().len(), 2);
+}
@@ -0,0 +1,111 @@
+use actix_restful::{Findable, NoQuery, PatchableModel, RequestContext, RestfulPathInfo, RestfulRoutes};
+use actix_restful_derive::Restful;
+use actix_web::http::{header, StatusCode};
+use actix_web::{test, web, App};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+#[derive(Serialize, Deserialize, Restful)]
+#[restful(scope = "/v1", path = "item", find, patch = Item)]
+struct Item {
+    id: i64,
+    content: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+}
+
+#[async_trait]
+impl Findable for Item {
+    type Id = i64;
+    type FindQuery = NoQuery;
+    type State = ();
+
+    async fn find(id: i64, _query: &NoQuery, _state: &(), _ctx: &RequestContext) -> Result<Box<Item>> {
+        let tags = vec![String::from("a"), String::from("b")];
+        Ok(Box::new(Item { id, content: String::from("found"), tags }))
+    }
+}
+
+#[async_trait]
+impl PatchableModel for Item {
+    type Model = Item;
+    type Output = Item;
+    type Query = NoQuery;
+    type State = ();
+
+    async fn patch(self, _id: i64, _query: &NoQuery, _state: &(), _ctx: &RequestContext) -> Result<Item> {
+        Ok(self)
+    }
+}
+
+/// The status and the json body of the response to the patch `body` of `content_type`
+macro_rules! patch {
+    ($content_type:expr, $body:expr) => {{
+        let mut app =
+            test::init_service(App::new().service(web::scope(Item::scope()).configure(Item::configure))).await;
+        let req = test::TestRequest::patch()
+            .uri("/v1/item/1")
+            .header(header::CONTENT_TYPE, $content_type)
+            .set_payload($body.to_string());
+        let res = test::call_service(&mut app, req.to_request()).await;
+        let status = res.status();
+        let body: Value = serde_json::from_slice(&test::read_body(res).await).unwrap_or(Value::Null);
+        (status, body)
+    }};
+}
+
+#[actix_rt::test]
+async fn merge_patch() {
+    let (status, body) = patch!("application/merge-patch+json", json!({ "content": "patched", "tags": null }));
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "id": 1, "content": "patched" }));
+
+    let (status, body) = patch!("application/json", json!({ "tags": ["c"] }));
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "id": 1, "content": "found", "tags": ["c"] }));
+}
+
+#[actix_rt::test]
+async fn merge_patch_answers_invalid_entities() {
+    let (status, body) = patch!("application/merge-patch+json", json!({ "content": 42 }));
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(body["status"], 422);
+}
+
+#[actix_rt::test]
+async fn other_documents_are_unsupported() {
+    let (status, body) = patch!("text/plain", "content=patched");
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_eq!(body["status"], 415);
+}
+
+#[actix_rt::test]
+async fn json_patch() {
+    let operations = json!([
+        { "op": "test", "path": "/content", "value": "found" },
+        { "op": "replace", "path": "/content", "value": "patched" },
+        { "op": "add", "path": "/tags/-", "value": "c" },
+        { "op": "move", "from": "/tags/0", "path": "/tags/2" },
+        { "op": "remove", "path": "/tags/0" }
+    ]);
+    let (status, body) = patch!("application/json-patch+json", operations);
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "id": 1, "content": "patched", "tags": ["c", "a"] }));
+}
+
+#[actix_rt::test]
+async fn json_patch_answers_failed_tests() {
+    let operations = json!([
+        { "op": "replace", "path": "/content", "value": "patched" },
+        { "op": "test", "path": "/content", "value": "found" }
+    ]);
+    let (status, body) = patch!("application/json-patch+json", operations);
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["status"], 409);
+    assert_eq!(body["detail"], "test failed on path \"/content\"");
+
+    let (status, _) = patch!("application/json-patch+json", json!([{ "op": "remove", "path": "/missing" }]));
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
@@ -0,0 +1,246 @@
+use actix_restful::{
+    Fields, FilterField, FilterKind, FilterQuery, Filterable, Findable, Listable, NoQuery, RequestContext,
+    RestfulPathInfo, RestfulRoutes, SortDirection, SortField, SortQuery, Sortable,
+};
+use actix_restful_derive::{actix_restful_info, Restful};
+use actix_web::http::StatusCode;
+use actix_web::web::Query;
+use actix_web::{test, web, App};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+#[actix_restful_info(scope = "/v1", path = "issue", filter = "id, created_at, type, title", sort = "created_at, title")]
+struct Issue {
+    id: i64,
+    created_at: String,
+    r#type: String,
+    #[serde(rename = "name")]
+    title: String,
+}
+
+#[derive(Serialize)]
+#[actix_restful_info(scope = "/v1", path = "comment")]
+struct Comment {
+    id: i64,
+    #[serde(flatten)]
+    issue: Issue,
+}
+
+#[derive(Deserialize)]
+struct ListQuery {
+    #[serde(flatten)]
+    filter: FilterQuery<Issue>,
+    #[serde(flatten)]
+    sort: SortQuery<Issue>,
+}
+
+#[derive(Serialize, Restful)]
+#[restful(scope = "/v1", path = "task", find, list, filter = "done", sort = "id, title")]
+struct Task {
+    id: i64,
+    title: String,
+    done: bool,
+}
+
+#[derive(Deserialize)]
+struct TaskQuery {
+    #[serde(flatten)]
+    filter: FilterQuery<Task>,
+    #[serde(flatten)]
+    sort: SortQuery<Task>,
+}
+
+/// The tasks listed by the routes of `Task`
+fn tasks() -> Vec<Task> {
+    vec![
+        Task { id: 1, title: String::from("write"), done: true },
+        Task { id: 2, title: String::from("test"), done: false },
+        Task { id: 3, title: String::from("ship"), done: false },
+    ]
+}
+
+#[async_trait]
+impl Findable for Task {
+    type Id = i64;
+    type FindQuery = NoQuery;
+    type State = ();
+
+    async fn find(id: i64, _query: &NoQuery, _state: &(), _ctx: &RequestContext) -> Result<Box<Task>> {
+        Ok(Box::new(tasks().remove(id as usize - 1)))
+    }
+}
+
+#[async_trait]
+impl Listable for Task {
+    type ListQuery = TaskQuery;
+    type ListResult = Vec<Task>;
+    type State = ();
+
+    async fn list(query: &TaskQuery, _state: &(), _ctx: &RequestContext) -> Result<Vec<Task>> {
+        let mut tasks = tasks();
+        tasks.retain(|task| query.filter.matches(task));
+        query.sort.sort(&mut tasks);
+        Ok(tasks)
+    }
+}
+
+/// The status and the json body of the response to a GET on `uri`, answered by the routes of `Task`
+macro_rules! get {
+    ($uri:expr) => {{
+        let mut app = test::init_service(App::new().service(web::scope(Task::scope()).configure(Task::configure))).await;
+        let res = test::call_service(&mut app, test::TestRequest::get().uri($uri).to_request()).await;
+        let status = res.status();
+        let body: Value = serde_json::from_slice(&test::read_body(res).await).unwrap();
+        (status, body)
+    }};
+}
+
+#[actix_rt::test]
+async fn lists_the_filtered_items() {
+    let (status, body) = get!("/v1/task?filter[done]=false");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body.as_array().unwrap().iter().map(|task| task["id"].clone()).collect::<Vec<_>>(), [2, 3]);
+
+}
+
+#[actix_rt::test]
+async fn unknown_filters_are_bad_requests() {
+    let (status, body) = get!("/v1/task?filter[title]=write");
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["detail"].as_str().unwrap().contains("title"), "{}", body);
+
+    for uri in ["/v1/task?filter[done]=maybe", "/v1/task?filter[done][gt]=false"] {
+        let (status, body) = get!(uri);
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", uri);
+        assert_eq!(body["status"], 400);
+    }
+}
+
+#[actix_rt::test]
+async fn lists_the_sorted_items() {
+    let (status, body) = get!("/v1/task?sort=-id");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body.as_array().unwrap().iter().map(|task| task["id"].clone()).collect::<Vec<_>>(), [3, 2, 1]);
+
+    let (_, body) = get!("/v1/task?filter[done]=false&sort=title");
+    assert_eq!(body.as_array().unwrap().iter().map(|task| task["title"].clone()).collect::<Vec<_>>(), ["ship", "test"]);
+
+    let (status, body) = get!("/v1/task?sort=done");
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["detail"].as_str().unwrap().contains("done"), "{}", body);
+}
+
+#[actix_rt::test]
+async fn picks_the_fields_of_the_items() {
+    let (status, body) = get!("/v1/task/1?fields=title");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "title": "write" }));
+
+    let (status, body) = get!("/v1/task?filter[done]=true&fields=id,done");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!([{ "id": 1, "done": true }]));
+
+    for uri in ["/v1/task?fields=name", "/v1/task/1?fields=name"] {
+        let (status, body) = get!(uri);
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", uri);
+        assert!(body["detail"].as_str().unwrap().contains("name"), "{}", body);
+    }
+}
+
+#[test]
+fn filters_on_the_serialized_names() {
+    let names: Vec<&str> = Issue::filter_fields().iter().map(|field| field.name).collect();
+    assert_eq!(names, ["id", "createdAt", "type", "name"]);
+    assert_eq!(Issue::filter_fields()[0], FilterField { name: "id", kind: FilterKind::Integer });
+
+    let query = Query::<ListQuery>::from_query("filter[createdAt][gte]=2026-01-01&filter[type]=bug").unwrap();
+    let issue = Issue { id: 1, created_at: String::from("2026-02-01"), r#type: String::from("bug"), title: String::new() };
+    assert!(query.filter.matches(&issue));
+    assert!(Query::<ListQuery>::from_query("filter[created_at]=2026-01-01").is_err());
+}
+
+#[test]
+fn sorts_on_the_serialized_names() {
+    assert_eq!(Issue::sort_fields(), ["createdAt", "name"]);
+
+    let query = Query::<ListQuery>::from_query("sort=-createdAt,name").unwrap();
+    assert_eq!(query.sort.fields()[0], SortField { field: "createdAt", direction: SortDirection::Desc });
+    let mut issues = vec![
+        Issue { id: 1, created_at: String::from("2026-01-01"), r#type: String::new(), title: String::from("b") },
+        Issue { id: 2, created_at: String::from("2026-02-01"), r#type: String::new(), title: String::from("a") },
+    ];
+    query.sort.sort(&mut issues);
+    assert_eq!(issues[0].id, 2);
+    assert!(Query::<ListQuery>::from_query("sort=created_at").is_err());
+}
+
+#[derive(Serialize)]
+#[actix_restful_info(scope = "/v1", path = "score", sort = "value")]
+struct Score {
+    value: Value,
+}
+
+#[test]
+fn sorts_the_values_of_any_type() {
+    let values = [
+        json!(null),
+        json!(false),
+        json!(true),
+        json!(-3),
+        json!(1),
+        json!(2.5),
+        json!(u64::MAX),
+        json!(""),
+        json!("a"),
+        json!([]),
+        json!([1, "a"]),
+        json!([2]),
+        json!({}),
+        json!({ "a": 1 }),
+        json!({ "a": "1" }),
+    ];
+    // shuffled and repeated, more items than an insertion sort is used for
+    let mut scores: Vec<Score> = (0..10)
+        .flat_map(|_| values.iter().rev().chain(values.iter()).step_by(2))
+        .map(|value| Score { value: value.clone() })
+        .collect();
+    let mut expected: Vec<Value> = values.iter().flat_map(|value| vec![value.clone(); 10]).collect();
+
+    Query::<SortQuery<Score>>::from_query("sort=value").unwrap().sort(&mut scores);
+    assert_eq!(scores.iter().map(|score| score.value.clone()).collect::<Vec<_>>(), expected);
+
+    Query::<SortQuery<Score>>::from_query("sort=-value").unwrap().sort(&mut scores);
+    expected.reverse();
+    assert_eq!(scores.iter().map(|score| score.value.clone()).collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn picks_the_serialized_names() {
+    assert_eq!(Issue::field_names(), ["id", "createdAt", "type", "name"]);
+    let issue = Issue { id: 1, created_at: String::from("2026-01-01"), r#type: String::from("bug"), title: String::new() };
+
+    let fields = Fields::parse("fields=id,createdAt").unwrap();
+    fields.check(Issue::field_names()).unwrap();
+    assert_eq!(fields.project_value(&issue).unwrap(), serde_json::json!({ "id": 1, "createdAt": "2026-01-01" }));
+    assert!(Fields::parse("fields=created_at").unwrap().check(Issue::field_names()).is_err());
+
+    let fields = Fields::parse("fields=").unwrap();
+    assert!(fields.is_empty());
+    assert_eq!(fields.project_value(&issue).unwrap()["name"], "");
+}
+
+#[test]
+fn picks_any_field_of_a_flattened_struct() {
+    assert!(Comment::field_names().is_empty());
+    let comment = Comment {
+        id: 1,
+        issue: Issue { id: 2, created_at: String::new(), r#type: String::from("bug"), title: String::new() },
+    };
+    let fields = Fields::parse("fields=type").unwrap();
+    fields.check(Comment::field_names()).unwrap();
+    assert_eq!(fields.project_value(&comment).unwrap(), serde_json::json!({ "type": "bug" }));
+}
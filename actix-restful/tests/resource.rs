@@ -0,0 +1,96 @@
+use actix_restful::{Findable, Listable, NoQuery, RequestContext, Resource, RestfulPathInfo};
+use actix_restful_derive::{actix_restful_info, HttpFind, HttpList};
+use actix_web::http::{header, StatusCode};
+use actix_web::{error, guard, test, web, App, HttpResponse};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Serialize;
+
+#[derive(Serialize, HttpFind, HttpList)]
+#[actix_restful_info(scope = "/v1", path = "item")]
+struct Item {
+    id: i64,
+}
+
+#[async_trait]
+impl Findable for Item {
+    type Id = i64;
+    type FindQuery = NoQuery;
+    type State = ();
+
+    async fn find(id: i64, _query: &NoQuery, _state: &(), _ctx: &RequestContext) -> Result<Box<Item>> {
+        Ok(Box::new(Item { id }))
+    }
+}
+
+#[async_trait]
+impl Listable for Item {
+    type ListQuery = NoQuery;
+    type ListResult = Vec<Item>;
+    type State = ();
+
+    async fn list(_query: &NoQuery, _state: &(), _ctx: &RequestContext) -> Result<Vec<Item>> {
+        Ok(vec![Item { id: 1 }])
+    }
+}
+
+#[actix_rt::test]
+async fn other_methods_are_not_allowed() {
+    let resource = Resource::<Item>::new().find().list().configure();
+    let mut app = test::init_service(App::new().service(web::scope(Item::scope()).configure(resource))).await;
+
+    let req = test::TestRequest::delete().uri("/v1/item/1").to_request();
+    let res = test::call_service(&mut app, req).await;
+    assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(res.headers().get(header::ALLOW).unwrap(), "GET");
+    assert_eq!(res.headers().get(header::CONTENT_TYPE).unwrap(), "application/problem+json");
+
+    let req = test::TestRequest::put().uri("/v1/item").to_request();
+    let res = test::call_service(&mut app, req).await;
+    assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(res.headers().get(header::ALLOW).unwrap(), "GET");
+}
+
+#[actix_rt::test]
+async fn guarded_routes_fall_through_to_not_found() {
+    let resource = Resource::<Item>::new()
+        .find()
+        .list_with(|resource| resource.guard(guard::Header("x-role", "admin")))
+        .configure();
+    let mut app = test::init_service(App::new().service(web::scope(Item::scope()).configure(resource))).await;
+
+    let req = test::TestRequest::get().uri("/v1/item").to_request();
+    let res = test::call_service(&mut app, req).await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    let req = test::TestRequest::get().uri("/v1/item").header("x-role", "admin").to_request();
+    let res = test::call_service(&mut app, req).await;
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let req = test::TestRequest::post().uri("/v1/item").to_request();
+    let res = test::call_service(&mut app, req).await;
+    assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+}
+
+#[actix_rt::test]
+async fn the_other_handlers_keep_their_settings() {
+    let resource = Resource::<Item>::new().find().list().configure();
+    let json_config = web::JsonConfig::default()
+        .error_handler(|err, _req| error::InternalError::from_response(err, HttpResponse::new(StatusCode::IM_A_TEAPOT)).into());
+    let echo = web::post().to(|body: web::Json<serde_json::Value>| std::future::ready(HttpResponse::Ok().json(body.0)));
+    let mut app = test::init_service(
+        App::new().service(
+            web::scope(Item::scope()).app_data(json_config).configure(resource).route("/echo", echo),
+        ),
+    )
+    .await;
+
+    let req = test::TestRequest::post().uri("/v1/echo").header(header::CONTENT_TYPE, "application/json");
+    let res = test::call_service(&mut app, req.set_payload("{").to_request()).await;
+    assert_eq!(res.status(), StatusCode::IM_A_TEAPOT);
+
+    let req = test::TestRequest::get().uri("/v1/item/x").to_request();
+    let res = test::call_service(&mut app, req).await;
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(res.headers().get(header::CONTENT_TYPE).unwrap(), "application/problem+json");
+}
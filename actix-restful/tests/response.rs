@@ -0,0 +1,172 @@
+use actix_restful::{
+    Deletable, Findable, NewModel, NoQuery, RequestContext, Resource, RestError, RestfulConfig, RestfulPathInfo,
+    RestfulRoutes,
+};
+use actix_restful_derive::Restful;
+use actix_web::http::{header, StatusCode};
+use actix_web::{test, web, App};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+
+#[derive(Serialize, Restful)]
+#[restful(scope = "/v1", path = "item", find, delete, delete_no_content, create = NewItem)]
+struct Item {
+    id: String,
+    content: String,
+}
+
+#[async_trait]
+impl Findable for Item {
+    type Id = String;
+    type FindQuery = NoQuery;
+    type State = ();
+
+    async fn find(id: String, _query: &NoQuery, _state: &(), _ctx: &RequestContext) -> Result<Box<Item>> {
+        Ok(Box::new(Item { id, content: String::from("found") }))
+    }
+}
+
+#[async_trait]
+impl Deletable for Item {
+    type DeleteQuery = NoQuery;
+    type DeleteResult = Item;
+
+    async fn delete(self, _query: &NoQuery, _state: &(), _ctx: &RequestContext) -> Result<Item> {
+        Ok(self)
+    }
+}
+
+#[derive(Deserialize)]
+struct NewItem {
+    id: String,
+    content: String,
+}
+
+#[async_trait]
+impl NewModel for NewItem {
+    type Output = Item;
+    type Query = NoQuery;
+    type State = ();
+
+    async fn save(self, _query: &NoQuery, _state: &(), _ctx: &RequestContext) -> Result<Item> {
+        Ok(Item { id: self.id, content: self.content })
+    }
+}
+
+/// The products created by the tests, by key
+#[derive(Default)]
+struct Store {
+    products: Mutex<HashMap<ProductKey, String>>,
+}
+
+/// The composite id of a product, `/v1/product/{tenant}/{code}`
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+struct ProductKey {
+    tenant: String,
+    code: String,
+}
+
+impl fmt::Display for ProductKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.tenant, self.code)
+    }
+}
+
+#[derive(Serialize, Deserialize, Restful)]
+#[restful(scope = "/v1", path = "product", id_pattern = "{tenant}/{code}", find, create = Product)]
+struct Product {
+    id: ProductKey,
+    name: String,
+}
+
+#[async_trait]
+impl Findable for Product {
+    type Id = ProductKey;
+    type FindQuery = NoQuery;
+    type State = Store;
+
+    async fn find(id: ProductKey, _query: &NoQuery, state: &Store, _ctx: &RequestContext) -> Result<Box<Product>> {
+        match state.products.lock().unwrap().get(&id) {
+            Some(name) => Ok(Box::new(Product { name: name.clone(), id })),
+            None => Err(RestError::NotFound(format!("product {} does not exist", id)).into()),
+        }
+    }
+}
+
+#[async_trait]
+impl NewModel for Product {
+    type Output = Product;
+    type Query = NoQuery;
+    type State = Store;
+
+    async fn save(self, _query: &NoQuery, state: &Store, _ctx: &RequestContext) -> Result<Product> {
+        state.products.lock().unwrap().insert(self.id.clone(), self.name.clone());
+        Ok(self)
+    }
+}
+
+#[actix_rt::test]
+async fn json_bodies() {
+    let resource = Resource::<Item>::new().find().configure();
+    let mut app = test::init_service(App::new().service(web::scope(Item::scope()).configure(resource))).await;
+    let res = test::call_service(&mut app, test::TestRequest::get().uri("/v1/item/1").to_request()).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers().get(header::CONTENT_TYPE).unwrap(), "application/json");
+    assert_eq!(test::read_body(res).await, r#"{"id":"1","content":"found"}"#);
+}
+
+#[actix_rt::test]
+async fn pretty_json_bodies() {
+    let resource = Resource::<Item>::new().find().configure();
+    let config = RestfulConfig::default().pretty(true);
+    let mut app =
+        test::init_service(App::new().app_data(config).service(web::scope(Item::scope()).configure(resource))).await;
+    let res = test::call_service(&mut app, test::TestRequest::get().uri("/v1/item/1").to_request()).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers().get(header::CONTENT_TYPE).unwrap(), "application/json");
+    assert_eq!(test::read_body(res).await, "{\n  \"id\": \"1\",\n  \"content\": \"found\"\n}");
+}
+
+#[actix_rt::test]
+async fn create_answers_the_encoded_location() {
+    let mut app = test::init_service(App::new().service(web::scope(Item::scope()).configure(Item::configure))).await;
+    let req = test::TestRequest::post().uri("/v1/item").set_json(&json!({ "id": "a b/c", "content": "new" }));
+    let res = test::call_service(&mut app, req.to_request()).await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+    assert_eq!(res.headers().get(header::LOCATION).unwrap(), "/v1/item/a%20b%2Fc");
+    let body: Value = serde_json::from_slice(&test::read_body(res).await).unwrap();
+    assert_eq!(body["id"], "a b/c");
+}
+
+#[actix_rt::test]
+async fn create_answers_the_location_of_a_composite_id() {
+    let state = web::Data::new(Store::default());
+    let mut app = test::init_service(
+        App::new().app_data(state).service(web::scope(Product::scope()).configure(Product::configure)),
+    )
+    .await;
+    let product = json!({ "id": { "tenant": "acme", "code": "X 1" }, "name": "Rocket skates" });
+    let req = test::TestRequest::post().uri("/v1/product").set_json(&product);
+    let res = test::call_service(&mut app, req.to_request()).await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+    let location = res.headers().get(header::LOCATION).unwrap().to_str().unwrap().to_string();
+    assert_eq!(location, "/v1/product/acme/X%201");
+
+    let res = test::call_service(&mut app, test::TestRequest::get().uri(&location).to_request()).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body: Value = serde_json::from_slice(&test::read_body(res).await).unwrap();
+    assert_eq!(body, product);
+}
+
+#[actix_rt::test]
+async fn delete_answers_no_content() {
+    let mut app = test::init_service(App::new().service(web::scope(Item::scope()).configure(Item::configure))).await;
+    let res = test::call_service(&mut app, test::TestRequest::delete().uri("/v1/item/1").to_request()).await;
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+    assert!(test::read_body(res).await.is_empty());
+}
@@ -0,0 +1,23 @@
+use actix_restful::Validate;
+use actix_restful_derive::Validate;
+use serde::Deserialize;
+
+#[derive(Deserialize, Validate)]
+#[serde(rename_all = "camelCase")]
+struct NewIssue {
+    #[restful(length(min = 1))]
+    display_name: String,
+    #[restful(length(min = 1))]
+    r#type: String,
+    #[serde(rename = "mail")]
+    #[restful(email)]
+    contact: Option<String>,
+}
+
+#[test]
+fn errors_name_the_serialized_fields() {
+    let issue = NewIssue { display_name: String::new(), r#type: String::new(), contact: Some(String::from("nobody")) };
+    let errors = issue.validate().unwrap_err();
+    let fields: Vec<&str> = errors.errors().iter().map(|err| err.field.as_str()).collect();
+    assert_eq!(fields, ["displayName", "type", "mail"]);
+}
@@ -0,0 +1,53 @@
+use serde::{Serialize, Deserialize};
+use actix_restful::{
+    Findable,
+    Listable,
+    NoQuery,
+    RequestContext,
+    RestError
+};
+use actix_restful_derive::{HttpFind, HttpList, actix_restful_info};
+use anyhow::Result;
+use async_trait::async_trait;
+use std::default::Default;
+use actix_web;
+
+type ListResult = Vec<Category>;
+// a slug
+type Id = String;
+
+const CATEGORIES: [&str; 3] = ["tools", "outdoor", "explosives"];
+
+// read only : DELETE /v1/category/{id} answers 405 Method Not Allowed
+#[derive(Default, Serialize, Deserialize, HttpFind, HttpList)]
+// no query parameters nor state
+#[actix_restful_info(scope = "/v1", path = "category")]
+pub struct Category {
+    id: Id,
+}
+
+#[async_trait]
+impl Findable for Category {
+    type Id = Id;
+    type FindQuery = NoQuery;
+    type State = ();
+
+    async fn find(id: Id, _query: &NoQuery, _state: &(), _ctx: &RequestContext) -> Result<Box<Category>> {
+        if CATEGORIES.contains(&id.as_str()) {
+            Ok(Box::new(Category { id }))
+        } else {
+            Err(RestError::NotFound(format!("category {} does not exist", id)).into())
+        }
+    }
+}
+
+#[async_trait]
+impl Listable for Category {
+    type ListQuery = NoQuery;
+    type ListResult = ListResult;
+    type State = ();
+
+    async fn list(_query: &NoQuery, _state: &(), _ctx: &RequestContext) -> Result<ListResult> {
+        Ok(CATEGORIES.iter().map(|id| Category { id: id.to_string() }).collect())
+    }
+}
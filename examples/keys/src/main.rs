@@ -0,0 +1,35 @@
+mod category;
+mod product;
+
+use actix_restful::{gen_endpoint, RestfulPathInfo};
+use category::Category;
+use product::{NewProduct, Product, ProductKey, UpdatableProduct};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+pub struct AppState {
+    pub products: Mutex<HashMap<ProductKey, String>>,
+}
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()>{
+    let products = vec![
+        (ProductKey { tenant: String::from("acme"), code: String::from("X1") }, String::from("Rocket skates")),
+        (ProductKey { tenant: String::from("acme"), code: String::from("X2") }, String::from("Giant magnet")),
+    ];
+    let state = actix_web::web::Data::new(AppState {
+        products: Mutex::new(products.into_iter().collect()),
+    });
+    actix_web::HttpServer::new(move || {
+        actix_web::App::new()
+            .service(
+                actix_web::web::scope(Product::scope())
+                    .configure(gen_endpoint!(Category => find, list))
+                    .configure(gen_endpoint!(Product => find, list, create(NewProduct), update(UpdatableProduct), delete))
+            )
+            .app_data(state.clone())
+    })
+        .bind(("127.0.0.1", 8085))?
+        .run()
+        .await
+}
@@ -0,0 +1,130 @@
+use serde::{Serialize, Deserialize};
+use actix_restful::{
+    Deletable,
+    Findable,
+    Listable,
+    NewModel,
+    RequestContext,
+    RestError,
+    UpdatableModel
+};
+use actix_restful_derive::{HttpCreate, HttpFindListDelete, HttpUpdate, actix_restful_info};
+use anyhow::Result;
+use async_trait::async_trait;
+use std::default::Default;
+use std::fmt;
+use actix_web;
+use crate::AppState;
+
+#[derive(Default, Deserialize)]
+pub struct FindQuery {}
+#[derive(Deserialize)]
+pub struct ListQuery {}
+#[derive(Deserialize)]
+pub struct DeleteQuery {}
+type ListResult = Vec<Product>;
+type DeleteResult = Product;
+#[derive(Deserialize)]
+pub struct SaveQuery {}
+#[derive(Deserialize)]
+pub struct UpdateQuery {}
+
+/// The composite id of a product, `/v1/product/{tenant}/{code}`
+#[derive(Clone, Default, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ProductKey {
+    pub tenant: String,
+    pub code: String,
+}
+
+// builds the Location header answered on create
+impl fmt::Display for ProductKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.tenant, self.code)
+    }
+}
+
+#[derive(Default, Serialize, Deserialize, HttpFindListDelete)]
+#[actix_restful_info(scope = "/v1", path = "product", id_pattern = "{tenant}/{code}")]
+pub struct Product {
+    id: ProductKey,
+    name: String,
+}
+
+#[async_trait]
+impl Findable for Product {
+    type Id = ProductKey;
+    type FindQuery = FindQuery;
+    type State = AppState;
+
+    async fn find(id: ProductKey, _query: &FindQuery, state: &AppState, _ctx: &RequestContext) -> Result<Box<Product>> {
+        let products = state.products.lock().unwrap();
+        match products.get(&id) {
+            Some(name) => Ok(Box::new(Product { name: name.clone(), id })),
+            None => Err(RestError::NotFound(format!("product {} does not exist", id)).into()),
+        }
+    }
+}
+
+#[async_trait]
+impl Listable for Product {
+    type ListQuery = ListQuery;
+    type ListResult = ListResult;
+    type State = AppState;
+
+    async fn list(_query: &ListQuery, state: &AppState, _ctx: &RequestContext) -> Result<ListResult> {
+        let products = state.products.lock().unwrap();
+        Ok(products.iter().map(|(id, name)| Product { id: id.clone(), name: name.clone() }).collect())
+    }
+}
+
+#[async_trait]
+impl Deletable for Product {
+    type DeleteQuery = DeleteQuery;
+    type DeleteResult = DeleteResult;
+
+    async fn delete(self: Self, _query: &DeleteQuery, state: &AppState, _ctx: &RequestContext) -> Result<DeleteResult> {
+        state.products.lock().unwrap().remove(&self.id);
+        Ok(self)
+    }
+}
+
+#[derive(Serialize, Deserialize, HttpCreate)]
+pub struct NewProduct {
+    id: ProductKey,
+    name: String,
+}
+
+#[async_trait]
+impl NewModel for NewProduct {
+    type Output = Product;
+    type Query = SaveQuery;
+    type State = AppState;
+
+    async fn save(self: Self, _query: &SaveQuery, state: &AppState, _ctx: &RequestContext) -> Result<Product> {
+        let mut products = state.products.lock().unwrap();
+        if products.contains_key(&self.id) {
+            return Err(RestError::Conflict(format!("product {} already exists", self.id)).into());
+        }
+        products.insert(self.id.clone(), self.name.clone());
+        Ok(Product { id: self.id, name: self.name })
+    }
+}
+
+// the id is taken from the path, a body carrying another one is answered 409 Conflict
+#[derive(Serialize, Deserialize, HttpUpdate)]
+pub struct UpdatableProduct {
+    name: String,
+}
+
+#[async_trait]
+impl UpdatableModel for UpdatableProduct {
+    type Model = Product;
+    type Output = Product;
+    type Query = UpdateQuery;
+    type State = AppState;
+
+    async fn update(self: Self, id: ProductKey, _current: Product, _query: &UpdateQuery, state: &AppState, _ctx: &RequestContext) -> Result<Product> {
+        state.products.lock().unwrap().insert(id.clone(), self.name.clone());
+        Ok(Product { id, name: self.name })
+    }
+}
@@ -0,0 +1,32 @@
+mod project;
+mod task;
+
+use actix_restful::{gen_endpoint, RestfulPathInfo};
+use project::Project;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use task::{NewTask, Task};
+
+pub struct AppState {
+    // project id => task ids
+    pub projects: Mutex<HashMap<i64, Vec<i64>>>,
+}
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()>{
+    let state = actix_web::web::Data::new(AppState {
+        projects: Mutex::new((1..=3).map(|id| (id, vec![1, 2])).collect()),
+    });
+    actix_web::HttpServer::new(move || {
+        actix_web::App::new()
+            .service(
+                actix_web::web::scope(Project::scope())
+                    .configure(gen_endpoint!(Project => find, list))
+                    .configure(gen_endpoint!(Task => find, list, create(NewTask), delete))
+            )
+            .app_data(state.clone())
+    })
+        .bind(("127.0.0.1", 8085))?
+        .run()
+        .await
+}
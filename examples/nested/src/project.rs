@@ -0,0 +1,69 @@
+use serde::{Serialize, Deserialize};
+use actix_restful::{
+    Deletable,
+    Findable,
+    Listable,
+    RequestContext,
+    RestError
+};
+use actix_restful_derive::{HttpFindListDelete, actix_restful_info};
+use anyhow::Result;
+use async_trait::async_trait;
+use std::default::Default;
+use actix_web;
+use crate::AppState;
+
+#[derive(Default, Deserialize)]
+pub struct FindQuery {}
+#[derive(Deserialize)]
+pub struct ListQuery {}
+#[derive(Deserialize)]
+pub struct DeleteQuery {}
+type ListResult = Vec<Project>;
+type DeleteResult = Project;
+pub type Id = i64;
+
+#[derive(Default, Serialize, Deserialize, HttpFindListDelete)]
+#[actix_restful_info(scope = "/v1", path = "project")]
+pub struct Project {
+    id: Id,
+    tasks: usize,
+}
+
+#[async_trait]
+impl Findable for Project {
+    type Id = Id;
+    type FindQuery = FindQuery;
+    type State = AppState;
+
+    async fn find(id: Id, _query: &FindQuery, state: &AppState, _ctx: &RequestContext) -> Result<Box<Project>> {
+        let projects = state.projects.lock().unwrap();
+        match projects.get(&id) {
+            Some(tasks) => Ok(Box::new(Project { id, tasks: tasks.len() })),
+            None => Err(RestError::NotFound(format!("project {} does not exist", id)).into()),
+        }
+    }
+}
+
+#[async_trait]
+impl Listable for Project {
+    type ListQuery = ListQuery;
+    type ListResult = ListResult;
+    type State = AppState;
+
+    async fn list(_query: &ListQuery, state: &AppState, _ctx: &RequestContext) -> Result<ListResult> {
+        let projects = state.projects.lock().unwrap();
+        Ok(projects.iter().map(|(id, tasks)| Project { id: *id, tasks: tasks.len() }).collect())
+    }
+}
+
+#[async_trait]
+impl Deletable for Project {
+    type DeleteQuery = DeleteQuery;
+    type DeleteResult = DeleteResult;
+
+    async fn delete(self: Self, _query: &DeleteQuery, state: &AppState, _ctx: &RequestContext) -> Result<DeleteResult> {
+        state.projects.lock().unwrap().remove(&self.id);
+        Ok(self)
+    }
+}
@@ -0,0 +1,98 @@
+use serde::{Serialize, Deserialize};
+use actix_restful::{
+    NestedDeletable,
+    NestedFindable,
+    NestedListable,
+    NestedNewModel,
+    RequestContext,
+    RestError
+};
+use actix_restful_derive::{HttpCreate, HttpFindListDelete, actix_restful_info};
+use anyhow::Result;
+use async_trait::async_trait;
+use std::default::Default;
+use actix_web;
+use crate::AppState;
+use crate::project::{self, Project};
+
+#[derive(Default, Deserialize)]
+pub struct FindQuery {}
+#[derive(Deserialize)]
+pub struct ListQuery {}
+#[derive(Deserialize)]
+pub struct DeleteQuery {}
+type ListResult = Vec<Task>;
+type DeleteResult = Task;
+#[derive(Deserialize)]
+pub struct SaveQuery {}
+type Id = i64;
+
+#[derive(Default, Serialize, Deserialize, HttpFindListDelete)]
+#[actix_restful_info(scope = "/v1", path = "task", parent = "Project", delete_no_content)]
+pub struct Task {
+    id: Id,
+    project_id: project::Id,
+}
+
+#[async_trait]
+impl NestedFindable for Task {
+    type ParentId = project::Id;
+    type Id = Id;
+    type FindQuery = FindQuery;
+    type State = AppState;
+
+    async fn find(project_id: project::Id, id: Id, _query: &FindQuery, state: &AppState, _ctx: &RequestContext) -> Result<Box<Task>> {
+        let projects = state.projects.lock().unwrap();
+        match projects.get(&project_id) {
+            Some(tasks) if tasks.contains(&id) => Ok(Box::new(Task { id, project_id })),
+            _ => Err(RestError::NotFound(format!("task {} does not exist", id)).into()),
+        }
+    }
+}
+
+#[async_trait]
+impl NestedListable for Task {
+    type ParentId = project::Id;
+    type ListQuery = ListQuery;
+    type ListResult = ListResult;
+    type State = AppState;
+
+    async fn list(project_id: project::Id, _query: &ListQuery, state: &AppState, _ctx: &RequestContext) -> Result<ListResult> {
+        let projects = state.projects.lock().unwrap();
+        let tasks = projects.get(&project_id).cloned().unwrap_or_default();
+        Ok(tasks.into_iter().map(|id| Task { id, project_id }).collect())
+    }
+}
+
+#[async_trait]
+impl NestedDeletable for Task {
+    type DeleteQuery = DeleteQuery;
+    type DeleteResult = DeleteResult;
+
+    async fn delete(self: Self, project_id: project::Id, _query: &DeleteQuery, state: &AppState, _ctx: &RequestContext) -> Result<DeleteResult> {
+        if let Some(tasks) = state.projects.lock().unwrap().get_mut(&project_id) {
+            tasks.retain(|id| *id != self.id);
+        }
+        Ok(self)
+    }
+}
+
+#[derive(Serialize, Deserialize, HttpCreate)]
+#[http_create(parent = Project)]
+pub struct NewTask {}
+
+#[async_trait]
+impl NestedNewModel for NewTask {
+    type ParentId = project::Id;
+    type Output = Task;
+    type Query = SaveQuery;
+    type State = AppState;
+
+    async fn save(self: Self, project_id: project::Id, _query: &SaveQuery, state: &AppState, _ctx: &RequestContext) -> Result<Task> {
+        let mut projects = state.projects.lock().unwrap();
+        let tasks = projects.get_mut(&project_id).ok_or_else(|| RestError::NotFound(String::from("project not found")))?;
+        let id = tasks.iter().max().copied().unwrap_or(0) + 1;
+        tasks.push(id);
+        Ok(Task { id, project_id })
+    }
+}
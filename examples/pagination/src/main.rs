@@ -1,20 +1,26 @@
 use serde::{Serialize, Deserialize};
 use actix_restful::{
-    HttpCreate,
-    HttpFindListDelete,
-    HttpUpdate,
-    Model,
+    CursorPage,
+    CursorQuery,
+    Deletable,
+    FilterQuery,
+    Findable,
+    Listable,
     NewModel,
+    PageQuery,
+    Paginated,
     UpdatableModel,
-    gen_endpoint,
-    RestfulPathInfo
+    RequestContext,
+    Resource,
+    RestfulConfig,
+    RestfulPathInfo,
+    SortQuery
 };
-use actix_restful_derive::{HttpCreate, HttpFindListDelete, HttpUpdate, actix_restful_info};
+use actix_restful_derive::{HttpCreate, HttpFindListDelete, HttpList, HttpUpdate, actix_restful_info};
 use anyhow::Result;
 use async_trait::async_trait;
 use std::default::Default;
 use actix_web;
-use serde_json;
 use chrono::prelude::*;
 
 struct AppState {}
@@ -22,17 +28,16 @@ struct AppState {}
 struct FindQuery {}
 #[derive(Deserialize)]
 struct ListQuery {
-    offset: usize,
-    limit: usize,
+    #[serde(flatten)]
+    page: PageQuery<20, 100>,
+    #[serde(flatten)]
+    filter: FilterQuery<Item>,
+    #[serde(flatten)]
+    sort: SortQuery<Item>,
 }
 #[derive(Deserialize)]
 struct DeleteQuery {}
-#[derive(Serialize)]
-struct ListResult{
-    limit: usize,
-    offset: usize,
-    results: Vec<Item>
-}
+type ListResult = Paginated<Item>;
 type DeleteResult = Item;
 #[derive(Deserialize)]
 struct SaveQuery {}
@@ -41,8 +46,7 @@ struct UpdateQuery {}
 type Id = i64;
 
 #[derive(Default, Serialize, Deserialize, HttpFindListDelete)]
-#[http_find_list_delete(Id, FindQuery, ListQuery, DeleteQuery, AppState)]
-#[actix_restful_info(scope = "/v1", path = "item")]
+#[actix_restful_info(scope = "/v1", path = "item", filter = "id: integer, content, created_at", sort = "id, created_at")]
 struct Item {
     id: Id,
     content: String,
@@ -52,8 +56,12 @@ struct Item {
 }
 
 #[async_trait]
-impl Model<Id, FindQuery, ListQuery, ListResult, DeleteQuery, DeleteResult, AppState> for Item {
-    async fn find(id: Id, _query: &FindQuery, _state: &AppState) -> Result<Box<Item>> {
+impl Findable for Item {
+    type Id = Id;
+    type FindQuery = FindQuery;
+    type State = AppState;
+
+    async fn find(id: Id, _query: &FindQuery, _state: &AppState, _ctx: &RequestContext) -> Result<Box<Item>> {
         // fetch from somwhere with id and return result
         Ok(
             Box::new(
@@ -67,7 +75,15 @@ impl Model<Id, FindQuery, ListQuery, ListResult, DeleteQuery, DeleteResult, AppS
             )
         )
     }
-    async fn list(query: &ListQuery, _state: &AppState) -> Result<ListResult> {
+}
+
+#[async_trait]
+impl Listable for Item {
+    type ListQuery = ListQuery;
+    type ListResult = ListResult;
+    type State = AppState;
+
+    async fn list(query: &ListQuery, _state: &AppState, _ctx: &RequestContext) -> Result<ListResult> {
         // list
         let mut res = Vec::new();
         for i in 1..500{
@@ -79,15 +95,18 @@ impl Model<Id, FindQuery, ListQuery, ListResult, DeleteQuery, DeleteResult, AppS
                 created_at: None,
             });
         }
-        let paginated: Vec<Item> = res.into_iter().skip(query.offset).take(query.limit).collect();
-        let output = ListResult {
-            limit: query.limit,
-            offset: query.offset,
-            results: paginated
-        };
-        Ok(output)
+        res.retain(|item| query.filter.matches(item));
+        query.sort.sort(&mut res);
+        Ok(Paginated::slice(res, &query.page))
     }
-    async fn delete(mut self: Self, _query: &DeleteQuery, _state: &AppState) -> Result<DeleteResult> {
+}
+
+#[async_trait]
+impl Deletable for Item {
+    type DeleteQuery = DeleteQuery;
+    type DeleteResult = DeleteResult;
+
+    async fn delete(mut self: Self, _query: &DeleteQuery, _state: &AppState, _ctx: &RequestContext) -> Result<DeleteResult> {
         // hard or soft delete
         let utc: DateTime<Utc> = Utc::now();
         self.deleted_at = Some(utc);
@@ -96,13 +115,16 @@ impl Model<Id, FindQuery, ListQuery, ListResult, DeleteQuery, DeleteResult, AppS
 }
 
 #[derive(Serialize, Deserialize, HttpCreate)]
-#[http_create(SaveQuery, AppState)]
 struct NewItem {
     content: String,
 }
 #[async_trait]
-impl NewModel<Item, SaveQuery, AppState> for NewItem {
-    async fn save(self: Self, _query: &SaveQuery, _state: &AppState) -> Result<Item> {
+impl NewModel for NewItem {
+    type Output = Item;
+    type Query = SaveQuery;
+    type State = AppState;
+
+    async fn save(self: Self, _query: &SaveQuery, _state: &AppState, _ctx: &RequestContext) -> Result<Item> {
         // persist, and return Item entity
         let utc: DateTime<Utc> = Utc::now();
         Ok(Item{
@@ -116,15 +138,19 @@ impl NewModel<Item, SaveQuery, AppState> for NewItem {
 }
 
 #[derive(Serialize, Deserialize, HttpUpdate)]
-#[http_update(Id, UpdateQuery, Item, FindQuery, AppState)]
 struct UpdatableItem {
     id: Id,
     content: String,
     updated_at: Option<DateTime<Utc>>,
 }
 #[async_trait]
-impl UpdatableModel<UpdatableItem, UpdateQuery, AppState> for UpdatableItem {
-    async fn update(mut self: Self, _query: &UpdateQuery, _state: &AppState) -> Result<UpdatableItem> {
+impl UpdatableModel for UpdatableItem {
+    type Model = Item;
+    type Output = UpdatableItem;
+    type Query = UpdateQuery;
+    type State = AppState;
+
+    async fn update(mut self: Self, _id: Id, _current: Item, _query: &UpdateQuery, _state: &AppState, _ctx: &RequestContext) -> Result<UpdatableItem> {
         // update in db
         let utc: DateTime<Utc> = Utc::now();
         self.updated_at = Some(utc);
@@ -132,11 +158,55 @@ impl UpdatableModel<UpdatableItem, UpdateQuery, AppState> for UpdatableItem {
     }
 }
 
+#[derive(Serialize, HttpList)]
+#[actix_restful_info(scope = "/v1", path = "event")]
+struct Event {
+    id: Id,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+struct EventQuery {
+    #[serde(flatten)]
+    page: CursorQuery<20, 100>,
+}
+
+#[async_trait]
+impl Listable for Event {
+    type ListQuery = EventQuery;
+    type ListResult = CursorPage<Event>;
+    type State = AppState;
+
+    async fn list(query: &EventQuery, _state: &AppState, ctx: &RequestContext) -> Result<CursorPage<Event>> {
+        // the events after the cursor, ordered by their creation then their id
+        let after: Option<(DateTime<Utc>, Id)> = query.page.after(ctx)?;
+        let start = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let events = (1..500)
+            .map(|id| Event { id, created_at: start + chrono::Duration::minutes(id / 2) })
+            .filter(|event| after.map_or(true, |after| (event.created_at, event.id) > after))
+            .take(query.page.limit() + 1)
+            .collect();
+        Ok(CursorPage::new(events, &query.page, ctx, |event| (event.created_at, event.id))?)
+    }
+}
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()>{
     actix_web::HttpServer::new(|| {
         actix_web::App::new()
-            .service(actix_web::web::scope(Item::scope()).configure(gen_endpoint!(Item, NewItem, UpdatableItem)))
+            .app_data(RestfulConfig::default().cursor_key("change me, shared by the instances of the app"))
+            .service(
+                actix_web::web::scope(Item::scope()).configure(
+                    Resource::<Item>::new()
+                        .find()
+                        .list()
+                        .create::<NewItem>()
+                        .update::<UpdatableItem>()
+                        .delete()
+                        .configure()
+                )
+                .configure(Resource::<Event>::new().list().configure())
+            )
             .data(AppState{})
     })
         .bind(("127.0.0.1", 8085))?
@@ -1,40 +1,32 @@
 use serde::{Serialize, Deserialize};
 use actix_restful::{
-    HttpCreate,
-    HttpFindListDelete,
-    HttpUpdate,
-    Model,
+    Deletable,
+    Findable,
+    Listable,
     NewModel,
+    NoQuery,
     UpdatableModel,
-    gen_endpoint,
-    RestfulPathInfo
+    PatchableModel,
+    RequestContext,
+    RestfulConfig,
+    RestfulPathInfo,
+    RestfulRoutes
 };
-use actix_restful_derive::{HttpCreate, HttpFindListDelete, HttpUpdate, actix_restful_info};
+use actix_restful_derive::{Restful, Validate};
 use anyhow::Result;
 use async_trait::async_trait;
 use std::default::Default;
 use actix_web;
-use serde_json;
 use chrono::prelude::*;
 
 struct AppState {}
-#[derive(Default, Deserialize)]
-struct FindQuery {}
-#[derive(Deserialize)]
-struct ListQuery {}
-#[derive(Deserialize)]
-struct DeleteQuery {}
 type ListResult = Vec<Item>;
 type DeleteResult = Item;
-#[derive(Deserialize)]
-struct SaveQuery {}
-#[derive(Deserialize)]
-struct UpdateQuery {}
 type Id = i64;
 
-#[derive(Default, Serialize, Deserialize, HttpFindListDelete)]
-#[http_find_list_delete(Id, FindQuery, ListQuery, DeleteQuery, AppState)]
-#[actix_restful_info(scope = "/v1", path = "item")]
+// the types of the handlers are read from the model traits
+#[derive(Default, Serialize, Deserialize, Restful)]
+#[restful(scope = "/v1", path = "item", create = NewItem, update = UpdatableItem, patch = UpdatableItem, body_limit = 4096)]
 struct Item {
     id: Id,
     content: String,
@@ -44,8 +36,12 @@ struct Item {
 }
 
 #[async_trait]
-impl Model<Id, FindQuery, ListQuery, ListResult, DeleteQuery, DeleteResult, AppState> for Item {
-    async fn find(id: Id, _query: &FindQuery, _state: &AppState) -> Result<Box<Item>> {
+impl Findable for Item {
+    type Id = Id;
+    type FindQuery = NoQuery;
+    type State = AppState;
+
+    async fn find(id: Id, _query: &NoQuery, _state: &AppState, _ctx: &RequestContext) -> Result<Box<Item>> {
         // fetch from somwhere with id and return result
         Ok(
             Box::new(
@@ -59,7 +55,15 @@ impl Model<Id, FindQuery, ListQuery, ListResult, DeleteQuery, DeleteResult, AppS
             )
         )
     }
-    async fn list(_query: &ListQuery, _state: &AppState) -> Result<ListResult> {
+}
+
+#[async_trait]
+impl Listable for Item {
+    type ListQuery = NoQuery;
+    type ListResult = ListResult;
+    type State = AppState;
+
+    async fn list(_query: &NoQuery, _state: &AppState, _ctx: &RequestContext) -> Result<ListResult> {
         // list
         let mut res = Vec::new();
         for i in 0..2{
@@ -73,7 +77,14 @@ impl Model<Id, FindQuery, ListQuery, ListResult, DeleteQuery, DeleteResult, AppS
         }
         Ok(res)
     }
-    async fn delete(mut self: Self, _query: &DeleteQuery, _state: &AppState) -> Result<DeleteResult> {
+}
+
+#[async_trait]
+impl Deletable for Item {
+    type DeleteQuery = NoQuery;
+    type DeleteResult = DeleteResult;
+
+    async fn delete(mut self: Self, _query: &NoQuery, _state: &AppState, _ctx: &RequestContext) -> Result<DeleteResult> {
         // hard or soft delete
         let utc: DateTime<Utc> = Utc::now();
         self.deleted_at = Some(utc);
@@ -81,14 +92,19 @@ impl Model<Id, FindQuery, ListQuery, ListResult, DeleteQuery, DeleteResult, AppS
     }
 }
 
-#[derive(Serialize, Deserialize, HttpCreate)]
-#[http_create(SaveQuery, AppState)]
+// the payloads failing their rules are answered 422
+#[derive(Serialize, Deserialize, Validate)]
 struct NewItem {
+    #[restful(length(min = 1, max = 200))]
     content: String,
 }
 #[async_trait]
-impl NewModel<Item, SaveQuery, AppState> for NewItem {
-    async fn save(self: Self, _query: &SaveQuery, _state: &AppState) -> Result<Item> {
+impl NewModel for NewItem {
+    type Output = Item;
+    type Query = NoQuery;
+    type State = AppState;
+
+    async fn save(self: Self, _query: &NoQuery, _state: &AppState, _ctx: &RequestContext) -> Result<Item> {
         // persist, and return Item entity
         let utc: DateTime<Utc> = Utc::now();
         Ok(Item{
@@ -101,29 +117,49 @@ impl NewModel<Item, SaveQuery, AppState> for NewItem {
     }
 }
 
-#[derive(Serialize, Deserialize, HttpUpdate)]
-#[http_update(Id, UpdateQuery, Item, FindQuery, AppState)]
+#[derive(Serialize, Deserialize, Validate)]
 struct UpdatableItem {
     id: Id,
+    #[restful(length(min = 1, max = 200))]
     content: String,
     updated_at: Option<DateTime<Utc>>,
 }
 #[async_trait]
-impl UpdatableModel<UpdatableItem, UpdateQuery, AppState> for UpdatableItem {
-    async fn update(mut self: Self, _query: &UpdateQuery, _state: &AppState) -> Result<UpdatableItem> {
+impl UpdatableModel for UpdatableItem {
+    type Model = Item;
+    type Output = UpdatableItem;
+    type Query = NoQuery;
+    type State = AppState;
+
+    async fn update(mut self: Self, _id: Id, _current: Item, _query: &NoQuery, _state: &AppState, _ctx: &RequestContext) -> Result<UpdatableItem> {
         // update in db
         let utc: DateTime<Utc> = Utc::now();
         self.updated_at = Some(utc);
         Ok(self)
     }
 }
+#[async_trait]
+impl PatchableModel for UpdatableItem {
+    type Model = Item;
+    type Output = UpdatableItem;
+    type Query = NoQuery;
+    type State = AppState;
+
+    async fn patch(mut self: Self, _id: Id, _query: &NoQuery, _state: &AppState, _ctx: &RequestContext) -> Result<UpdatableItem> {
+        // the patch is already merged on top of the stored Item, update in db
+        let utc: DateTime<Utc> = Utc::now();
+        self.updated_at = Some(utc);
+        Ok(self)
+    }
+}
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()>{
     actix_web::HttpServer::new(|| {
         actix_web::App::new()
-            .service(actix_web::web::scope(Item::scope()).configure(gen_endpoint!(Item, NewItem, UpdatableItem)))
+            .service(actix_web::web::scope(Item::scope()).configure(Item::configure))
             .data(AppState{})
+            .app_data(RestfulConfig::default().pretty(cfg!(debug_assertions)))
     })
         .bind(("127.0.0.1", 8085))?
         .run()